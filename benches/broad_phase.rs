@@ -0,0 +1,81 @@
+use std::hint::black_box;
+
+use bevy::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use example_bevy::physics::{BroadPhase, Collider, CollisionLayers};
+
+const BALL_COUNTS: [usize; 3] = [100, 1_000, 10_000];
+const BALL_SIZE: f32 = 20.0;
+/// ボール1つあたりの領域の幅（数を増やしても密度を変えない）
+const BALL_SPACING: f32 = 60.0;
+
+/// 決まった乱数列でボールを散らばらせる関数
+fn balls(count: usize) -> Vec<(Entity, Isometry2d)> {
+    let side = (count as f32).sqrt() * BALL_SPACING;
+    let mut seed = 0x2545_f491_u32;
+    let mut random = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / u32::MAX as f32
+    };
+    (0..count)
+        .map(|i| {
+            let position = Vec2::new(random(), random()) * side;
+            (Entity::from_raw(i as u32), Isometry2d::from_translation(position))
+        })
+        .collect()
+}
+
+/// 全ての組み合わせを狭域判定する関数
+fn all_pairs(collider: &Collider, balls: &[(Entity, Isometry2d)]) -> usize {
+    let mut contacts = 0;
+    for (i, (_, isometry1)) in balls.iter().enumerate() {
+        for (_, isometry2) in &balls[i + 1..] {
+            if collider.contact(*isometry1, collider, *isometry2).is_some() {
+                contacts += 1;
+            }
+        }
+    }
+    contacts
+}
+
+/// 空間ハッシュで絞り込んだ組だけを狭域判定する関数
+fn spatial_hash(broad_phase: &mut BroadPhase, collider: &Collider, balls: &[(Entity, Isometry2d)]) -> usize {
+    broad_phase.clear();
+    for (entity, isometry) in balls {
+        broad_phase.insert(*entity, collider.aabb(*isometry), CollisionLayers::default());
+    }
+    broad_phase.update_pairs();
+    broad_phase.pairs().iter()
+        .filter(|(entity1, entity2)| {
+            let isometry1 = balls[entity1.index() as usize].1;
+            let isometry2 = balls[entity2.index() as usize].1;
+            collider.contact(isometry1, collider, isometry2).is_some()
+        })
+        .count()
+}
+
+fn broad_phase(c: &mut Criterion) {
+    let collider = Collider::circle(BALL_SIZE);
+    let mut group = c.benchmark_group("ball_collisions");
+    group.sample_size(10);
+    for count in BALL_COUNTS {
+        let balls = balls(count);
+        let mut broad_phase = BroadPhase::default();
+        // 同じ接触を見つけていることを確認してから計測する
+        assert_eq!(all_pairs(&collider, &balls), spatial_hash(&mut broad_phase, &collider, &balls));
+
+        group.bench_with_input(BenchmarkId::new("all_pairs", count), &balls, |b, balls| {
+            b.iter(|| all_pairs(&collider, black_box(balls)))
+        });
+        group.bench_with_input(BenchmarkId::new("spatial_hash", count), &balls, |b, balls| {
+            b.iter(|| spatial_hash(&mut broad_phase, &collider, black_box(balls)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, broad_phase);
+criterion_main!(benches);
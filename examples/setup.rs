@@ -1,38 +1,26 @@
-use bevy::{
-    prelude::*,
-    log::LogPlugin,
-    asset::AssetMetaCheck,
-};
+use std::process;
+
+use bevy::prelude::*;
 
-const GAMETITLE: &str = "セットアップ";
-const WINDOW_SIZE: Vec2 = Vec2::new(640.0, 480.0);
-const BACKGROUND_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
-const LOG_FILTER: &str = "info,wgpu_core=warn,wgpu_hal=warn,setup=debug";
+use example_bevy::{
+    ExampleBasePlugin,
+    config::GameConfig,
+    demos::{Demo, setup::{self, SetupDemoPlugin}},
+};
 
 fn main() {
+    // ウィンドウやログの設定は起動前に必要なため、設定ファイルを先に読み込む
+    let config = match GameConfig::load_file(setup::PATH_CONFIG) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}: {}", setup::PATH_CONFIG, error);
+            process::exit(1);
+        }
+    };
+
     App::new()
-        .add_plugins(DefaultPlugins
-            .set(WindowPlugin {
-                primary_window: Some(Window {
-                    resolution: WINDOW_SIZE.into(),
-                    title: GAMETITLE.to_string(),
-                    ..Default::default()
-                }),
-                ..Default::default()
-            })
-            .set(ImagePlugin::default_nearest())
-            .set(LogPlugin {
-                filter: LOG_FILTER.into(),
-                level: bevy::log::Level::DEBUG,
-                ..Default::default()
-            })
-            .set(AssetPlugin {
-                meta_check: AssetMetaCheck::Never,
-                ..Default::default()
-            })
-        )
-        .insert_resource(ClearColor(BACKGROUND_COLOR))
-        .insert_resource(Time::<Fixed>::from_seconds(1.0 / 60.0))
-        .run()
-    ;
+        .add_plugins(ExampleBasePlugin::from(&config))
+        .insert_state(Demo::Setup)
+        .add_plugins(SetupDemoPlugin)
+        .run();
 }
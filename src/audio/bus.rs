@@ -0,0 +1,193 @@
+use std::path::PathBuf;
+
+use bevy::{
+    prelude::*,
+    audio::Volume,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{AudioSettings, settings};
+
+/// 音量を調整する系統
+/// `AudioPlayer`と同じエンティティに追加すると、その系統の音量で再生される
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioBus {
+    /// 全ての音に掛かる系統
+    Master,
+    Bgm,
+    Sfx,
+}
+
+impl AudioBus {
+    /// 全ての系統
+    pub const ALL: [AudioBus; 3] = [AudioBus::Master, AudioBus::Bgm, AudioBus::Sfx];
+
+    /// 系統の名前を返す関数
+    pub fn label(&self) -> &'static str {
+        match self {
+            AudioBus::Master => "Master",
+            AudioBus::Bgm => "BGM",
+            AudioBus::Sfx => "SFX",
+        }
+    }
+}
+
+/// 系統の音量に掛ける、音ごとの音量（0.0から1.0）
+/// フェードインやフェードアウトに使う
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct AudioGain(pub f32);
+
+impl Default for AudioGain {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// 1つの系統の音量とミュート、一時停止の状態
+/// 一時停止は保存しない
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct BusState {
+    /// 0.0から1.0の音量
+    pub volume: f32,
+    pub muted: bool,
+    #[serde(skip)]
+    pub paused: bool,
+}
+
+impl Default for BusState {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            paused: false,
+        }
+    }
+}
+
+/// 系統ごとの音量を管理するリソース
+/// 変更すると、再生中の音にも反映される
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AudioBuses {
+    pub master: BusState,
+    pub bgm: BusState,
+    pub sfx: BusState,
+}
+
+impl AudioBuses {
+    /// 系統の状態を返す関数
+    pub fn get(&self, bus: AudioBus) -> &BusState {
+        match bus {
+            AudioBus::Master => &self.master,
+            AudioBus::Bgm => &self.bgm,
+            AudioBus::Sfx => &self.sfx,
+        }
+    }
+
+    /// 系統の状態を変更するために返す関数
+    pub fn get_mut(&mut self, bus: AudioBus) -> &mut BusState {
+        match bus {
+            AudioBus::Master => &mut self.master,
+            AudioBus::Bgm => &mut self.bgm,
+            AudioBus::Sfx => &mut self.sfx,
+        }
+    }
+
+    /// マスターの音量を掛けた、系統の実際の音量を返す関数
+    pub fn volume(&self, bus: AudioBus) -> f32 {
+        match bus {
+            AudioBus::Master => self.master.volume,
+            _ => self.master.volume * self.get(bus).volume,
+        }
+    }
+
+    /// 系統かマスターがミュートされているかを返す関数
+    pub fn is_muted(&self, bus: AudioBus) -> bool {
+        self.master.muted || self.get(bus).muted
+    }
+
+    /// 系統かマスターが一時停止されているかを返す関数
+    pub fn is_paused(&self, bus: AudioBus) -> bool {
+        self.master.paused || self.get(bus).paused
+    }
+
+    /// 一時停止を解除した、保存する状態を返す関数
+    pub fn without_pause(&self) -> Self {
+        let mut buses = self.clone();
+        for bus in AudioBus::ALL {
+            buses.get_mut(bus).paused = false;
+        }
+        buses
+    }
+
+    /// 再生の設定に、系統の音量とミュート、一時停止の状態を反映する関数
+    /// 音を生成するときに使うと、最初から系統の音量で再生される
+    pub fn playback_settings(&self, bus: AudioBus, settings: PlaybackSettings) -> PlaybackSettings {
+        PlaybackSettings {
+            volume: Volume::Linear(self.volume(bus)),
+            muted: self.is_muted(bus),
+            paused: self.is_paused(bus),
+            ..settings
+        }
+    }
+}
+
+/// 系統の音量とミュート、一時停止を、再生中の音に反映するプラグイン
+/// 系統の状態は設定ファイルから読み込み、変更されたら保存する
+#[derive(Default)]
+pub struct AudioBusPlugin {
+    /// 設定ファイルのパス（無ければ設定フォルダの設定ファイル）
+    pub settings_path: Option<PathBuf>,
+}
+
+impl Plugin for AudioBusPlugin {
+    fn build(&self, app: &mut App) {
+        // 音を生成するサンプルのセットアップより前に、保存した状態を読み込む
+        let path = self.settings_path.clone().or_else(|| AudioSettings::default_path(app.world()));
+        let settings = AudioSettings::load_or_default(path);
+        app
+            .insert_resource(settings.buses().clone())
+            .insert_resource(settings)
+            .add_systems(PostUpdate, (
+                settings::save_audio_settings,
+                apply_audio_buses,
+            ))
+        ;
+    }
+}
+
+/// 系統のプラグインを追加する関数
+/// 複数のサンプルから呼ばれても、1回だけ追加する
+pub fn add_audio_buses(app: &mut App) {
+    if !app.is_plugin_added::<AudioBusPlugin>() {
+        app.add_plugins(AudioBusPlugin::default());
+    }
+}
+
+/// 系統が変更されたときと、音の再生が始まったときと、音ごとの音量が変わったときに、系統の状態を反映する関数
+pub(super) fn apply_audio_buses(
+    mut query: Query<(&mut AudioSink, &AudioBus, Option<Ref<AudioGain>>)>,
+    buses: Res<AudioBuses>,
+) {
+    info_once!("apply_audio_buses");
+
+    for (mut sink, bus, gain) in &mut query {
+        let gain_changed = gain.as_ref().is_some_and(|gain| gain.is_changed());
+        if !(buses.is_changed() || sink.is_added() || gain_changed) {
+            continue;
+        }
+        let gain = gain.map_or(1.0, |gain| gain.0);
+        sink.set_volume(Volume::Linear(buses.volume(*bus) * gain));
+        match (buses.is_muted(*bus), sink.is_muted()) {
+            (true, false) => sink.mute(),
+            (false, true) => sink.unmute(),
+            _ => {}
+        }
+        match (buses.is_paused(*bus), sink.is_paused()) {
+            (true, false) => sink.pause(),
+            (false, true) => sink.play(),
+            _ => {}
+        }
+    }
+}
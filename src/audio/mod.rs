@@ -0,0 +1,7 @@
+pub mod bus;
+pub mod music;
+pub mod settings;
+
+pub use bus::{AudioBus, AudioBusPlugin, AudioBuses, AudioGain, BusState, add_audio_buses};
+pub use music::{MusicPlayer, MusicPlugin, MusicTrack, RepeatMode, add_music};
+pub use settings::{AudioSettings, AudioSettingsError};
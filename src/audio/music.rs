@@ -0,0 +1,369 @@
+use bevy::{
+    prelude::*,
+    audio::Volume,
+};
+
+use super::{AudioBus, AudioBuses, AudioGain, add_audio_buses, bus};
+
+/// 曲を切り替えるときのクロスフェードの既定の秒数
+pub const DEFAULT_CROSSFADE: f32 = 1.0;
+
+/// プレイリストの繰り返し方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepeatMode {
+    /// 最後の曲が終わったら止める
+    Off,
+    /// 同じ曲を繰り返す
+    One,
+    /// 最後の曲が終わったら最初の曲に戻る
+    #[default]
+    All,
+}
+
+impl RepeatMode {
+    /// Off、All、Oneの順に次の繰り返し方を返す関数
+    pub fn cycle(self) -> Self {
+        match self {
+            RepeatMode::Off => RepeatMode::All,
+            RepeatMode::All => RepeatMode::One,
+            RepeatMode::One => RepeatMode::Off,
+        }
+    }
+}
+
+/// 次のフレームで反映する再生の変更
+#[derive(Debug, Clone, PartialEq)]
+enum MusicRequest {
+    Play {
+        track: Handle<AudioSource>,
+        looping: bool,
+        fade_in: bool,
+    },
+    Stop,
+}
+
+/// BGMの系統で曲を再生するリソース
+/// プレイリストの曲を順番かシャッフルで再生し、曲を切り替えるときはクロスフェードする
+/// 曲が最後まで再生されて次の曲に移るときは、曲の長さが分からないためクロスフェードせず、次の曲をすぐに聞こえるようにする
+/// プレイリストに無い曲（ステートごとの曲など）も`play_track`で再生できる
+#[derive(Resource, Debug, Clone)]
+pub struct MusicPlayer {
+    playlist: Vec<Handle<AudioSource>>,
+    /// 再生する順番（プレイリストの番号）
+    order: Vec<usize>,
+    /// 再生する順番の中の位置
+    position: Option<usize>,
+    shuffle: bool,
+    pub repeat: RepeatMode,
+    /// クロスフェードの秒数（0.0なら即座に切り替える）
+    pub crossfade: f32,
+    /// 再生中の曲
+    current: Option<Handle<AudioSource>>,
+    request: Option<MusicRequest>,
+    rng: fastrand::Rng,
+}
+
+impl Default for MusicPlayer {
+    fn default() -> Self {
+        Self {
+            playlist: Vec::new(),
+            order: Vec::new(),
+            position: None,
+            shuffle: false,
+            repeat: RepeatMode::default(),
+            crossfade: DEFAULT_CROSSFADE,
+            current: None,
+            request: None,
+            rng: fastrand::Rng::new(),
+        }
+    }
+}
+
+impl MusicPlayer {
+    /// プレイリストを指定して作成する関数
+    pub fn new(playlist: impl IntoIterator<Item = Handle<AudioSource>>) -> Self {
+        let mut player = Self::default();
+        player.set_playlist(playlist);
+        player
+    }
+
+    /// クロスフェードの秒数を変更する関数
+    pub fn with_crossfade(mut self, seconds: f32) -> Self {
+        self.crossfade = seconds;
+        self
+    }
+
+    /// シャッフルの乱数の種を指定する関数
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = fastrand::Rng::with_seed(seed);
+        self
+    }
+
+    /// プレイリストを入れ替える関数
+    /// 再生中の曲はそのまま流れ、次に再生するときに新しいプレイリストの最初から再生する
+    pub fn set_playlist(&mut self, playlist: impl IntoIterator<Item = Handle<AudioSource>>) {
+        self.playlist = playlist.into_iter().collect();
+        self.position = None;
+        self.reorder(None);
+    }
+
+    /// プレイリストを返す関数
+    pub fn playlist(&self) -> &[Handle<AudioSource>] {
+        &self.playlist
+    }
+
+    /// 再生中の曲を返す関数
+    pub fn current_track(&self) -> Option<&Handle<AudioSource>> {
+        self.current.as_ref()
+    }
+
+    /// 再生中の曲のプレイリストの番号を返す関数
+    /// プレイリストに無い曲を再生中か、止まっている場合は`None`を返す
+    pub fn current_index(&self) -> Option<usize> {
+        self.current.as_ref()?;
+        self.position.map(|position| self.order[position])
+    }
+
+    /// 曲を再生中かを返す関数
+    pub fn is_playing(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// シャッフルしているかを返す関数
+    pub fn shuffle(&self) -> bool {
+        self.shuffle
+    }
+
+    /// シャッフルを切り替える関数
+    /// 再生中の曲は止めずに、次の曲から新しい順番で再生する
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        if self.shuffle == shuffle {
+            return;
+        }
+        self.shuffle = shuffle;
+        let index = self.current_index();
+        self.reorder(index);
+        self.position = index.and_then(|index| self.order.iter().position(|&i| i == index));
+    }
+
+    /// プレイリストの曲を再生する関数
+    /// 止める前に再生していた曲か、最初の曲から再生する
+    pub fn play(&mut self) {
+        if self.current_index().is_some() {
+            return;
+        }
+        self.start(self.position.unwrap_or(0));
+    }
+
+    /// プレイリストの番号を指定して再生する関数
+    pub fn play_index(&mut self, index: usize) {
+        if let Some(position) = self.order.iter().position(|&i| i == index) {
+            self.start(position);
+        }
+    }
+
+    /// プレイリストの次の曲を再生する関数
+    /// 最後の曲の次は最初の曲に戻る
+    pub fn next(&mut self) {
+        if self.playlist.is_empty() {
+            return;
+        }
+        let position = self.position.map_or(0, |position| position + 1);
+        if position >= self.order.len() {
+            self.reorder(None);
+            self.start(0);
+        } else {
+            self.start(position);
+        }
+    }
+
+    /// プレイリストの前の曲を再生する関数
+    /// 最初の曲の前は最後の曲に戻る
+    pub fn previous(&mut self) {
+        if self.playlist.is_empty() {
+            return;
+        }
+        let position = match self.position {
+            Some(0) | None => self.order.len() - 1,
+            Some(position) => position - 1,
+        };
+        self.start(position);
+    }
+
+    /// プレイリストに無い曲を再生する関数
+    /// 同じ曲を再生中なら、最初から再生し直さない
+    /// 繰り返さない曲（ジングル）は、フェードインせずにすぐに聞こえるようにする
+    pub fn play_track(&mut self, track: Handle<AudioSource>, looping: bool) {
+        if self.current.as_ref() == Some(&track) {
+            return;
+        }
+        self.position = None;
+        self.current = Some(track.clone());
+        self.request = Some(MusicRequest::Play { track, looping, fade_in: looping });
+    }
+
+    /// 再生中の曲をフェードアウトして止める関数
+    pub fn stop(&mut self) {
+        self.current = None;
+        self.request = Some(MusicRequest::Stop);
+    }
+
+    /// 曲が最後まで再生されたときに、繰り返し方に従って次の曲を再生する関数
+    /// 前の曲はもう聞こえないので、次の曲はフェードインしない
+    pub fn finish_track(&mut self) {
+        let Some(position) = self.position.filter(|_| self.is_playing()) else {
+            // プレイリストに無い曲は1回だけ再生する
+            self.current = None;
+            return;
+        };
+        match self.repeat {
+            RepeatMode::One => self.start(position),
+            RepeatMode::All => self.next(),
+            RepeatMode::Off if position + 1 < self.order.len() => self.next(),
+            RepeatMode::Off => self.stop(),
+        }
+        if let Some(MusicRequest::Play { fade_in, .. }) = &mut self.request {
+            *fade_in = false;
+        }
+    }
+
+    /// 再生する順番の位置の曲を再生する関数
+    fn start(&mut self, position: usize) {
+        let Some(track) = self.order.get(position).map(|&index| self.playlist[index].clone()) else {
+            return;
+        };
+        self.position = Some(position);
+        self.current = Some(track.clone());
+        self.request = Some(MusicRequest::Play { track, looping: false, fade_in: true });
+    }
+
+    /// 再生する順番を作り直す関数
+    /// シャッフルする場合、`first`の曲を最初にする
+    fn reorder(&mut self, first: Option<usize>) {
+        self.order = (0..self.playlist.len()).collect();
+        if self.shuffle {
+            self.rng.shuffle(&mut self.order);
+            if let Some(first) = first.and_then(|first| self.order.iter().position(|&i| i == first)) {
+                self.order.swap(0, first);
+            }
+        }
+    }
+}
+
+/// `MusicPlayer`が再生している曲
+/// 再生中の曲はフェードインし、再生中でなくなった曲はフェードアウトしてから削除される
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MusicTrack {
+    pub current: bool,
+}
+
+/// `MusicPlayer`の曲を再生するプラグイン
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        add_audio_buses(app);
+        app
+            .init_resource::<MusicPlayer>()
+            .add_systems(PostUpdate, (
+                finish_music_tracks,
+                play_music_requests,
+                fade_music,
+            ).chain().before(bus::apply_audio_buses))
+        ;
+    }
+}
+
+/// 曲を再生するプラグインを追加する関数
+/// 複数のサンプルから呼ばれても、1回だけ追加する
+pub fn add_music(app: &mut App) {
+    if !app.is_plugin_added::<MusicPlugin>() {
+        app.add_plugins(MusicPlugin);
+    }
+}
+
+/// 最後まで再生された曲を削除し、次の曲を再生する関数
+/// 次の曲はクロスフェードせずに、すぐに聞こえる音量で始まる
+fn finish_music_tracks(
+    mut commands: Commands,
+    query: Query<(Entity, &MusicTrack, &AudioSink)>,
+    mut player: ResMut<MusicPlayer>,
+) {
+    info_once!("finish_music_tracks");
+
+    for (entity, track, sink) in &query {
+        if !sink.empty() {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        if track.current {
+            debug!("music track finished");
+            player.finish_track();
+        }
+    }
+}
+
+/// 再生の変更を反映し、曲を生成するか、再生中の曲をフェードアウトさせる関数
+fn play_music_requests(
+    mut commands: Commands,
+    mut player: ResMut<MusicPlayer>,
+    mut query: Query<(&mut MusicTrack, &AudioGain)>,
+    buses: Res<AudioBuses>,
+) {
+    info_once!("play_music_requests");
+
+    // 変更が無いフレームで、プレイヤーが変更されたことにしない
+    let Some(request) = player.bypass_change_detection().request.take() else {
+        return;
+    };
+    // 聞こえている曲がある場合だけ、次の曲をフェードインさせる
+    let audible = query.iter().any(|(track, gain)| track.current && gain.0 > 0.0);
+    for (mut track, _) in &mut query {
+        track.current = false;
+    }
+    let MusicRequest::Play { track, looping, fade_in } = request else {
+        debug!("stop music");
+        return;
+    };
+    debug!("play music: {:?}", track.path());
+    let gain = if fade_in && audible && player.crossfade > 0.0 { 0.0 } else { 1.0 };
+    let mode = if looping { PlaybackSettings::LOOP } else { PlaybackSettings::ONCE };
+    let mut settings = buses.playback_settings(AudioBus::Bgm, mode);
+    settings.volume = Volume::Linear(buses.volume(AudioBus::Bgm) * gain);
+    commands.spawn((
+        AudioPlayer(track),
+        settings,
+        AudioBus::Bgm,
+        AudioGain(gain),
+        MusicTrack { current: true },
+    ));
+}
+
+/// 再生中の曲をフェードインさせ、それ以外の曲をフェードアウトさせて削除する関数
+fn fade_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    player: Res<MusicPlayer>,
+    mut query: Query<(Entity, &MusicTrack, &mut AudioGain)>,
+) {
+    info_once!("fade_music");
+
+    let step = if player.crossfade > 0.0 {
+        time.delta_secs() / player.crossfade
+    } else {
+        1.0
+    };
+    for (entity, track, mut gain) in &mut query {
+        let target = if track.current { 1.0 } else { 0.0 };
+        if gain.0 != target {
+            gain.0 = if gain.0 < target {
+                (gain.0 + step).min(target)
+            } else {
+                (gain.0 - step).max(target)
+            };
+        }
+        if !track.current && gain.0 <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use thiserror::Error;
+
+use crate::storage;
+use super::AudioBuses;
+
+/// 音の設定を保存するファイル名
+pub const AUDIO_SETTINGS_FILE: &str = "audio_settings.ron";
+
+/// 音の設定の読み込みや保存に失敗したときのエラー
+#[derive(Debug, Error)]
+pub enum AudioSettingsError {
+    #[error("could not access audio settings file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse audio settings file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("could not serialize audio settings: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// 系統ごとの音量とミュートを、設定ファイルに保存するリソース
+/// 保存先が無い場合は、保存せずにメモリ上だけで管理する
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct AudioSettings {
+    buses: AudioBuses,
+    path: Option<PathBuf>,
+}
+
+impl AudioSettings {
+    /// 系統の状態と保存先を指定して作成する関数
+    pub fn new(buses: AudioBuses, path: Option<PathBuf>) -> Self {
+        Self {
+            buses: buses.without_pause(),
+            path,
+        }
+    }
+
+    /// 設定フォルダ（`StorageRoot`があればその中）の、設定ファイルのパスを返す関数
+    pub fn default_path(world: &World) -> Option<PathBuf> {
+        storage::world_config_dir(world).map(|dir| dir.join(AUDIO_SETTINGS_FILE))
+    }
+
+    /// 保存先のファイルから読み込む関数
+    /// 読み込めない場合は警告を出し、既定の音量から始める
+    pub fn load_or_default(path: Option<PathBuf>) -> Self {
+        let Some(path) = path else {
+            warn!("config directory not found; audio settings will not be saved");
+            return Self::new(AudioBuses::default(), None);
+        };
+        Self::load(&path).unwrap_or_else(|error| {
+            warn!("{error}; starting with default audio settings");
+            Self::new(AudioBuses::default(), Some(path))
+        })
+    }
+
+    /// ファイルから設定を読み込む関数
+    /// ファイルが存在しない場合は既定の設定を返す
+    pub fn load(path: &Path) -> Result<Self, AudioSettingsError> {
+        let buses = match fs::read(path) {
+            Ok(bytes) => ron::de::from_bytes(&bytes)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => AudioBuses::default(),
+            Err(error) => return Err(error.into()),
+        };
+        Ok(Self::new(buses, Some(path.to_path_buf())))
+    }
+
+    /// 設定をファイルに保存する関数
+    /// 保存先が無い場合は何もしない
+    pub fn save(&self) -> Result<(), AudioSettingsError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = ron::ser::to_string_pretty(&self.buses, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text)?;
+        debug!("saved audio settings to {}", path.display());
+        Ok(())
+    }
+
+    /// 保存する系統の状態を返す関数
+    pub fn buses(&self) -> &AudioBuses {
+        &self.buses
+    }
+
+    /// 保存先を返す関数
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+/// 系統の音量かミュートが変わったら、設定ファイルに保存する関数
+/// 一時停止だけが変わった場合は保存しない
+pub(super) fn save_audio_settings(
+    buses: Res<AudioBuses>,
+    mut settings: ResMut<AudioSettings>,
+) {
+    info_once!("save_audio_settings");
+
+    if !buses.is_changed() || buses.is_added() {
+        return;
+    }
+    let buses = buses.without_pause();
+    if settings.buses == buses {
+        return;
+    }
+    settings.buses = buses;
+    if let Err(error) = settings.save() {
+        warn!("{error}");
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use bevy::{
+    prelude::*,
+    asset::{AssetLoader, LoadContext, io::{Reader, file::FileAssetReader}},
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    ExampleBasePlugin,
+    WINDOW_SIZE,
+    LOG_FILTER,
+    FIXED_TIMESTEP,
+    demos::setup,
+};
+
+const ASSET_DIR: &str = "assets";
+const MAX_TIMESTEP_HZ: f64 = 1000.0;
+
+/// ゲームの設定ファイル（RON形式）
+/// 書かれていない項目は既定値が使われる
+#[derive(Asset, TypePath, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub title: String,
+    pub window_size: (f32, f32),
+    pub background_color: (f32, f32, f32),
+    pub log_filter: String,
+    pub fixed_timestep_hz: f64,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        let background_color = setup::BACKGROUND_COLOR.to_srgba();
+        Self {
+            title: setup::GAMETITLE.to_string(),
+            window_size: (WINDOW_SIZE.x, WINDOW_SIZE.y),
+            background_color: (background_color.red, background_color.green, background_color.blue),
+            log_filter: LOG_FILTER.to_string(),
+            fixed_timestep_hz: 1.0 / FIXED_TIMESTEP,
+        }
+    }
+}
+
+/// 設定ファイルの読み込みや検証に失敗したときのエラー
+#[derive(Debug, Error)]
+pub enum GameConfigError {
+    #[error("could not read config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse config file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid value for `{field}`: {value} ({reason})")]
+    Invalid {
+        field: &'static str,
+        value: String,
+        reason: &'static str,
+    },
+}
+
+impl GameConfig {
+    /// RON形式の文字列から設定を読み込み、検証する関数
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, GameConfigError> {
+        let config: Self = ron::de::from_bytes(bytes)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// アセットフォルダから設定ファイルを同期的に読み込む関数
+    /// ウィンドウやログの設定はアプリ起動前に必要なため、アセットサーバーを使わずに読み込む
+    /// ファイルが存在しない場合は既定値を返す
+    pub fn load_file(path: &str) -> Result<Self, GameConfigError> {
+        let path = FileAssetReader::get_base_path().join(ASSET_DIR).join(path);
+        match fs::read(Path::new(&path)) {
+            Ok(bytes) => Self::from_ron(&bytes),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// 設定値が正しい範囲にあるかを検証する関数
+    pub fn validate(&self) -> Result<(), GameConfigError> {
+        let invalid = |field, value: String, reason| Err(GameConfigError::Invalid { field, value, reason });
+
+        if self.title.trim().is_empty() {
+            return invalid("title", format!("{:?}", self.title), "must not be empty");
+        }
+        let (width, height) = self.window_size;
+        if !(width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0) {
+            return invalid("window_size", format!("{:?}", self.window_size), "must be positive");
+        }
+        let (red, green, blue) = self.background_color;
+        if ![red, green, blue].iter().all(|c| (0.0..=1.0).contains(c)) {
+            return invalid("background_color", format!("{:?}", self.background_color), "each component must be between 0.0 and 1.0");
+        }
+        if self.log_filter.trim().is_empty() {
+            return invalid("log_filter", format!("{:?}", self.log_filter), "must not be empty");
+        }
+        if !(self.fixed_timestep_hz > 0.0 && self.fixed_timestep_hz <= MAX_TIMESTEP_HZ) {
+            return invalid("fixed_timestep_hz", format!("{}", self.fixed_timestep_hz), "must be greater than 0 and at most 1000");
+        }
+        Ok(())
+    }
+
+    /// 背景色を返す関数
+    pub fn background_color(&self) -> Color {
+        let (red, green, blue) = self.background_color;
+        Color::srgb(red, green, blue)
+    }
+}
+
+impl From<&GameConfig> for ExampleBasePlugin {
+    fn from(config: &GameConfig) -> Self {
+        Self {
+            window_size: Vec2::new(config.window_size.0, config.window_size.1),
+            log_filter: config.log_filter.clone(),
+            fixed_timestep: 1.0 / config.fixed_timestep_hz,
+            ..ExampleBasePlugin::new(&config.title)
+        }
+    }
+}
+
+/// 設定ファイルをアセットとして読み込むローダー
+/// 検証に失敗した場合は読み込みエラーとなり、以前の設定がそのまま使われる
+#[derive(Default)]
+pub struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = GameConfigError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        GameConfig::from_ron(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+/// 設定ファイルの読み込みと、変更時の再適用を行うプラグイン
+/// 再適用されるのはウィンドウのタイトル、背景色、固定タイムステップ
+pub struct GameConfigPlugin;
+
+impl Plugin for GameConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<GameConfig>()
+            .init_asset_loader::<GameConfigLoader>()
+            .add_systems(Update, apply_game_config)
+        ;
+    }
+}
+
+/// 設定ファイルが読み込まれたり変更されたりしたら、設定を反映する関数
+fn apply_game_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    configs: Res<Assets<GameConfig>>,
+    mut window_query: Query<&mut Window>,
+    mut clear_color: ResMut<ClearColor>,
+    mut time: ResMut<Time<Fixed>>,
+) {
+    info_once!("apply_game_config");
+
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        let Some(config) = configs.get(*id) else {
+            continue;
+        };
+
+        debug!("apply game config: {:?}", config);
+        for mut window in &mut window_query {
+            window.title = config.title.clone();
+        }
+        clear_color.0 = config.background_color();
+        time.set_timestep_hz(config.fixed_timestep_hz);
+    }
+}
@@ -0,0 +1,305 @@
+use bevy::prelude::*;
+
+use crate::audio::{MusicPlayer, add_music};
+use super::Demo;
+
+pub const GAMETITLE: &str = "ステート遷移";
+
+pub const KEY_MAINMENU_TO_INGAME: KeyCode = KeyCode::KeyI;
+pub const KEY_INGAME_TO_PAUSE: KeyCode = KeyCode::KeyP;
+pub const KEY_INGAME_TO_GAMEOVER: KeyCode = KeyCode::KeyG;
+pub const KEY_PAUSE_TO_INGAME: KeyCode = KeyCode::KeyP;
+pub const KEY_GAMEOVER_TO_MAINMENU: KeyCode = KeyCode::KeyB;
+pub const KEY_GAMEOVER_TO_INGAME: KeyCode = KeyCode::KeyR;
+
+// 同梱の音源が少ないため、メインメニューとゲームは同じ音源を使う
+// ゲームはプレイリストから再生するので、メインメニューの曲からクロスフェードして最初から流れる
+const PATH_MUSIC_MAINMENU: &str = "sounds/bgm.ogg";
+const PATH_MUSIC_INGAME_PLAYLIST: [&str; 1] = ["sounds/bgm.ogg"];
+const PATH_MUSIC_GAMEOVER: &str = "sounds/click.ogg";
+
+#[derive(Component)]
+pub struct Mainmenu;
+
+#[derive(Component)]
+pub struct Ingame;
+
+#[derive(Component)]
+pub struct Pause;
+
+#[derive(Component)]
+pub struct Gameover;
+
+/// ステートで流す曲
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateTrack {
+    /// プレイリストに無い曲
+    Track {
+        track: Handle<AudioSource>,
+        looping: bool,
+    },
+    /// `MusicPlayer`のプレイリスト
+    Playlist,
+}
+
+/// ステートごとに流す曲を管理するリソース
+/// ゲームの曲は`MusicPlayer`のプレイリストから流す
+/// 曲が無いステート（ポーズ）では、前のステートの曲を流し続ける
+#[derive(Resource, Debug, Clone)]
+pub struct StateMusic {
+    pub mainmenu: Handle<AudioSource>,
+    /// 1回だけ流すジングル
+    pub gameover: Handle<AudioSource>,
+}
+
+impl StateMusic {
+    /// ステートで流す曲を返す関数
+    pub fn track(&self, state: &AppState) -> Option<StateTrack> {
+        match state {
+            AppState::Mainmenu => Some(StateTrack::Track { track: self.mainmenu.clone(), looping: true }),
+            AppState::Ingame => Some(StateTrack::Playlist),
+            AppState::Pause => None,
+            AppState::Gameover => Some(StateTrack::Track { track: self.gameover.clone(), looping: false }),
+        }
+    }
+}
+
+/// サンプル内の画面遷移を管理するステート
+/// ステート遷移のサンプルが起動している間だけ存在する
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash, Resource)]
+#[source(Demo = Demo::AppState)]
+pub enum AppState {
+    #[default]
+    Mainmenu,
+    Ingame,
+    Pause,
+    Gameover,
+}
+
+/// メインメニュー、ゲーム、ポーズ、ゲームオーバーを行き来するサンプルのプラグイン
+pub struct AppStateDemoPlugin;
+
+impl Plugin for AppStateDemoPlugin {
+    fn build(&self, app: &mut App) {
+        add_music(app);
+        app
+            .add_sub_state::<AppState>()
+            .add_systems(OnEnter(Demo::AppState), setup)
+            .add_systems(Update, play_state_music.run_if(state_changed::<AppState>))
+            .add_systems(OnExit(Demo::AppState), exit)
+            // メインメニュー
+            .add_systems(OnEnter(AppState::Mainmenu), mainmenu_setup)
+            .add_systems(Update, mainmenu_update.run_if(in_state(AppState::Mainmenu)))
+            .add_systems(OnExit(AppState::Mainmenu), mainmenu_exit)
+            // ゲーム
+            .add_systems(OnEnter(AppState::Ingame), ingame_setup)
+            .add_systems(Update, ingame_update.run_if(in_state(AppState::Ingame)))
+            .add_systems(OnExit(AppState::Ingame), ingame_exit)
+            // ポーズ
+            .add_systems(OnEnter(AppState::Pause), pause_setup)
+            .add_systems(Update, pause_update.run_if(in_state(AppState::Pause)))
+            .add_systems(OnExit(AppState::Pause), pause_exit)
+            // ゲームオーバー
+            .add_systems(OnEnter(AppState::Gameover), gameover_setup)
+            .add_systems(Update, gameover_update.run_if(in_state(AppState::Gameover)))
+            .add_systems(OnExit(AppState::Gameover), gameover_exit)
+        ;
+    }
+}
+
+/// カメラとステートごとの曲、ゲームのプレイリストをセットアップする関数
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut player: ResMut<MusicPlayer>,
+) {
+    info_once!("setup");
+
+    commands.spawn((Camera2d, StateScoped(Demo::AppState)));
+    commands.insert_resource(StateMusic {
+        mainmenu: asset_server.load(PATH_MUSIC_MAINMENU),
+        gameover: asset_server.load(PATH_MUSIC_GAMEOVER),
+    });
+    player.set_playlist(PATH_MUSIC_INGAME_PLAYLIST.map(|path| asset_server.load(path)));
+}
+
+/// ステートが変わったら、そのステートの曲にクロスフェードする関数
+fn play_state_music(
+    state: Res<State<AppState>>,
+    music: Res<StateMusic>,
+    mut player: ResMut<MusicPlayer>,
+) {
+    info_once!("play_state_music");
+
+    match music.track(state.get()) {
+        Some(StateTrack::Track { track, looping }) => player.play_track(track, looping),
+        // ポーズから戻ったときは、再生中のプレイリストの曲を流し続ける
+        Some(StateTrack::Playlist) => player.play(),
+        None => {}
+    }
+}
+
+/// ステートごとの曲を削除し、曲をフェードアウトさせる関数
+fn exit(
+    mut commands: Commands,
+    mut player: ResMut<MusicPlayer>,
+) {
+    info_once!("exit");
+
+    commands.remove_resource::<StateMusic>();
+    player.stop();
+}
+
+/// メインメニューのセットアップを行う関数
+fn mainmenu_setup(mut commands: Commands) {
+    info_once!("mainmenu_setup");
+
+    let text = format!("State transition to Ingame with {:?}", KEY_MAINMENU_TO_INGAME);
+    commands.spawn((
+        Text2d(text),
+        Mainmenu,
+    ));
+}
+
+/// 特定のキーが押された時にMainmenuステートからIngameステートに遷移する関数
+fn mainmenu_update(
+    mut next_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    info_once!("mainmenu_update");
+
+    if keyboard_input.just_pressed(KEY_MAINMENU_TO_INGAME) {
+        next_state.set(AppState::Ingame);
+    }
+}
+
+/// Mainmenuコンポーネントを全て削除する関数
+fn mainmenu_exit(
+    mut commands: Commands,
+    query: Query<Entity, With<Mainmenu>>,
+) {
+    info_once!("mainmenu_exit");
+
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// ゲームのセットアップを行う関数
+fn ingame_setup(mut commands: Commands) {
+    info_once!("ingame_setup");
+
+    let text = format!(
+        "State transition to Pause with {:?}\nState translation to Gameover with {:?}",
+        KEY_INGAME_TO_PAUSE, KEY_INGAME_TO_GAMEOVER,
+    );
+    commands.spawn((
+        Text2d(text),
+        Ingame,
+    ));
+}
+
+/// 特定のキーが押された時にキーに対応するステートに遷移する関数
+fn ingame_update(
+    mut next_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    info_once!("ingame_update");
+
+    if keyboard_input.just_pressed(KEY_INGAME_TO_PAUSE) {
+        next_state.set(AppState::Pause);
+    }
+
+    if keyboard_input.just_pressed(KEY_INGAME_TO_GAMEOVER) {
+        next_state.set(AppState::Gameover);
+    }
+}
+
+/// Ingameコンポーネントを全て削除する関数
+fn ingame_exit(
+    mut commands: Commands,
+    query: Query<Entity, With<Ingame>>,
+) {
+    info_once!("ingame_exit");
+    
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// ポーズのセットアップを行う関数
+fn pause_setup(mut commands: Commands) {
+    info_once!("pause_setup");
+
+    let text = format!("State transition to Ingame with {:?}", KEY_PAUSE_TO_INGAME);
+    commands.spawn((
+        Text2d(text),
+        Pause,
+    ));
+}
+
+/// 特定のキーが押された時にPauseステートからIngameステートに遷移する関数
+fn pause_update(
+    mut next_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    info_once!("pause_update");
+
+    if keyboard_input.just_pressed(KEY_PAUSE_TO_INGAME) {
+        next_state.set(AppState::Ingame);
+    }
+}
+
+/// Pauseコンポーネントを全て削除する関数
+fn pause_exit(
+    mut commands: Commands,
+    query: Query<Entity, With<Pause>>,
+) {
+    info_once!("pause_exit");
+
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// ゲームオーバーのセットアップを行う関数
+fn gameover_setup(mut commands: Commands) {
+    info_once!("gameover_setup");
+
+    let text = format!(
+        "State transition to Mainmenu with {:?}\nState transition to Ingame with {:?}",
+        KEY_GAMEOVER_TO_MAINMENU, KEY_GAMEOVER_TO_INGAME,
+    );
+    commands.spawn((
+        Text2d(text),
+        Gameover,
+    ));
+}
+
+/// 特定のキーが押された時にキーに対応するステートに遷移する関数
+fn gameover_update(
+    mut next_state: ResMut<NextState<AppState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    info_once!("gameover_update");
+
+    if keyboard_input.just_pressed(KEY_GAMEOVER_TO_MAINMENU) {
+        next_state.set(AppState::Mainmenu);
+    }
+
+    if keyboard_input.just_pressed(KEY_GAMEOVER_TO_INGAME) {
+        next_state.set(AppState::Ingame);
+    }
+}
+
+/// Gameoverコンポーネントを全て削除する関数
+fn gameover_exit(
+    mut commands: Commands,
+    query: Query<Entity, With<Gameover>>,
+) {
+    info_once!("gameover_exit");
+
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,307 @@
+use bevy::prelude::*;
+use bevy::color::palettes::basic::*;
+
+use crate::{
+    audio::{AudioBus, AudioBuses, MusicPlayer, add_audio_buses, add_music},
+    slider::{Slider, SliderSet, add_slider, slider},
+};
+use super::Demo;
+
+pub const GAMETITLE: &str = "オーディオ";
+
+const PATH_SOUND_CLICK: &str = "sounds/click.ogg";
+/// BGMのプレイリスト
+const PATH_SOUND_PLAYLIST: [&str; 1] = ["sounds/bgm.ogg"];
+pub const KEY_NEXT_TRACK: KeyCode = KeyCode::KeyN;
+pub const KEY_PREVIOUS_TRACK: KeyCode = KeyCode::KeyB;
+pub const KEY_SHUFFLE: KeyCode = KeyCode::KeyS;
+pub const KEY_REPEAT: KeyCode = KeyCode::KeyR;
+const BUTTON_SIZE: Vec2 = Vec2::new(80.0, 40.0);
+const BUTTON_FONT_SIZE: f32 = 20.0;
+const BUTTON_BORDER_SIZE: f32 = 2.0;
+const BUTTON_BORDER_RADIUS: f32 = 5.0;
+const BUTTON_GAP: f32 = 10.0;
+const BUTTON_PAUSE_TEXT: &str = "Pause";
+const BUTTON_MUTE_TEXT: &str = "Mute.";
+const LABEL_WIDTH: f32 = 80.0;
+const SLIDER_SIZE: Vec2 = Vec2::new(200.0, 20.0);
+/// ミュートや一時停止しているボタンの背景色
+const BUTTON_ACTIVE_COLOR: Srgba = GRAY;
+
+#[derive(Resource, Deref, DerefMut)]
+struct ClickSound(Handle<AudioSource>);
+
+/// プレイリストの再生状態を表示するテキスト
+#[derive(Component, Debug)]
+pub struct MusicStatus;
+
+/// 系統の音量を変更するスライダー
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VolumeSlider(pub AudioBus);
+
+/// 系統のミュートか一時停止を切り替えるボタン
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusButton {
+    pub bus: AudioBus,
+    pub action: BusAction,
+}
+
+/// ボタンで切り替える系統の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusAction {
+    Mute,
+    Pause,
+}
+
+/// ここでは、以下の機能の実装の例が書かれています。
+/// - クリック音
+/// - BGMのプレイリスト（次の曲、前の曲、シャッフル、リピート）
+/// - Master/BGM/SFXの系統ごとの音量（スライダー）
+/// - 系統ごとの一時停止
+/// - 系統ごとのミュート
+pub struct AudioDemoPlugin;
+
+impl Plugin for AudioDemoPlugin {
+    fn build(&self, app: &mut App) {
+        add_audio_buses(app);
+        add_music(app);
+        add_slider(app);
+        app
+            .add_systems(OnEnter(Demo::Audio), setup)
+            .add_systems(Update, (
+                change_volume,
+                toggle_bus,
+                update_bus_buttons,
+                play_clicksound,
+                control_music,
+                update_music_status,
+            ).chain().after(SliderSet).run_if(in_state(Demo::Audio)))
+            .add_systems(OnExit(Demo::Audio), exit)
+        ;
+    }
+}
+
+/// 音源を再生するためのセットアップを行う
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    buses: Res<AudioBuses>,
+    mut player: ResMut<MusicPlayer>,
+) {
+    info_once!("setup");
+
+    // クリック音をリソースに登録
+    let sound = asset_server.load(PATH_SOUND_CLICK);
+    commands.insert_resource(ClickSound(sound));
+
+    // BGMのプレイリストを再生
+    player.set_playlist(PATH_SOUND_PLAYLIST.map(|path| asset_server.load(path)));
+    player.play();
+
+    // カメラを生成
+    commands.spawn((Camera2d, StateScoped(Demo::Audio)));
+
+    // 系統ごとに、名前とスライダーとボタンを並べた行を生成
+    let button_node = |bus, action| (
+        Button,
+        BusButton { bus, action },
+        Node {
+            width: Val::Px(BUTTON_SIZE.x),
+            height: Val::Px(BUTTON_SIZE.y),
+            border: UiRect::all(Val::Px(BUTTON_BORDER_SIZE)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        BorderColor(GRAY.into()),
+        BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS))
+    );
+    let closure_text_node = |text: &str| {
+        (
+            Text::new(text),
+            TextFont::from_font_size(BUTTON_FONT_SIZE),
+            TextColor(WHITE.into()),
+        )
+    };
+    let root = commands.spawn((
+        // ボタンルートを作成
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            display: Display::Flex,
+            row_gap: Val::Px(BUTTON_GAP),
+            ..Default::default()
+        },
+        StateScoped(Demo::Audio),
+    )).id();
+    commands.spawn((
+        closure_text_node(""),
+        MusicStatus,
+        ChildOf(root),
+    ));
+    for bus in AudioBus::ALL {
+        commands.spawn((
+            Node {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(BUTTON_GAP),
+                ..Default::default()
+            },
+            ChildOf(root),
+            children![
+                // 系統の名前
+                (
+                    closure_text_node(bus.label()),
+                    Node {
+                        width: Val::Px(LABEL_WIDTH),
+                        ..Default::default()
+                    },
+                ),
+                // 音量のスライダー
+                (
+                    slider(buses.get(bus).volume, SLIDER_SIZE),
+                    VolumeSlider(bus),
+                ),
+                // ポーズボタンを生成
+                (
+                    button_node(bus, BusAction::Pause),
+                    children![closure_text_node(BUTTON_PAUSE_TEXT)],
+                ),
+                // ミュートボタンを生成
+                (
+                    button_node(bus, BusAction::Mute),
+                    children![closure_text_node(BUTTON_MUTE_TEXT)],
+                ),
+            ],
+        ));
+    }
+}
+
+/// スライダーが動かされたら系統の音量を変更する
+fn change_volume(
+    slider_query: Query<(&Slider, &VolumeSlider), Changed<Slider>>,
+    mut buses: ResMut<AudioBuses>,
+) {
+    info_once!("change_volume");
+
+    for (slider, VolumeSlider(bus)) in &slider_query {
+        if buses.get(*bus).volume != slider.value {
+            debug!("{} volume: {}", bus.label(), slider.value);
+            buses.get_mut(*bus).volume = slider.value;
+        }
+    }
+}
+
+/// ボタンが押されたら系統のミュートか一時停止を切り替える
+fn toggle_bus(
+    button_query: Query<(&Interaction, &BusButton), Changed<Interaction>>,
+    mut buses: ResMut<AudioBuses>,
+) {
+    info_once!("toggle_bus");
+
+    for (interaction, button) in &button_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let state = buses.get_mut(button.bus);
+        match button.action {
+            BusAction::Mute => state.muted = !state.muted,
+            BusAction::Pause => state.paused = !state.paused,
+        }
+        debug!("{} {:?}: {:?}", button.bus.label(), button.action, state);
+    }
+}
+
+/// ミュートや一時停止しているボタンの背景色を変える
+fn update_bus_buttons(
+    mut button_query: Query<(&BusButton, &mut BackgroundColor)>,
+    buses: Res<AudioBuses>,
+) {
+    if !buses.is_changed() {
+        return;
+    }
+    for (button, mut color) in &mut button_query {
+        let state = buses.get(button.bus);
+        let active = match button.action {
+            BusAction::Mute => state.muted,
+            BusAction::Pause => state.paused,
+        };
+        *color = if active { BUTTON_ACTIVE_COLOR.into() } else { Color::NONE.into() };
+    }
+}
+
+/// 左クリックでクリック音がSFXの音量で再生される
+fn play_clicksound(
+    mut commands: Commands,
+    mouse_events: Res<ButtonInput<MouseButton>>,
+    sound: Res<ClickSound>,
+    buses: Res<AudioBuses>,
+) {
+    info_once!("play_clicksound");
+
+    if mouse_events.just_pressed(MouseButton::Left) {
+        debug!("play click sound");
+        commands.spawn((
+            AudioPlayer(sound.clone()),
+            buses.playback_settings(AudioBus::Sfx, PlaybackSettings::DESPAWN),
+            AudioBus::Sfx,
+        ));
+    }
+}
+
+/// キーでプレイリストの曲を切り替え、シャッフルとリピートを変更する
+fn control_music(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut player: ResMut<MusicPlayer>,
+) {
+    info_once!("control_music");
+
+    if keyboard_input.just_pressed(KEY_NEXT_TRACK) {
+        player.next();
+    }
+    if keyboard_input.just_pressed(KEY_PREVIOUS_TRACK) {
+        player.previous();
+    }
+    if keyboard_input.just_pressed(KEY_SHUFFLE) {
+        let shuffle = !player.shuffle();
+        player.set_shuffle(shuffle);
+        debug!("shuffle: {shuffle}");
+    }
+    if keyboard_input.just_pressed(KEY_REPEAT) {
+        player.repeat = player.repeat.cycle();
+        debug!("repeat: {:?}", player.repeat);
+    }
+}
+
+/// プレイリストの再生状態を表示する
+fn update_music_status(
+    player: Res<MusicPlayer>,
+    mut query: Query<&mut Text, With<MusicStatus>>,
+) {
+    if !player.is_changed() {
+        return;
+    }
+    let track = player.current_index().map_or_else(|| "-".to_string(), |index| format!("{}", index + 1));
+    let text = format!(
+        "Track {track}/{} [{:?}/{:?}]  Shuffle: {} [{:?}]  Repeat: {:?} [{:?}]",
+        player.playlist().len(), KEY_PREVIOUS_TRACK, KEY_NEXT_TRACK,
+        if player.shuffle() { "On" } else { "Off" }, KEY_SHUFFLE,
+        player.repeat, KEY_REPEAT,
+    );
+    for mut status in &mut query {
+        **status = text.clone();
+    }
+}
+
+/// クリック音のリソースを削除し、BGMをフェードアウトさせる
+fn exit(
+    mut commands: Commands,
+    mut player: ResMut<MusicPlayer>,
+) {
+    info_once!("exit");
+
+    commands.remove_resource::<ClickSound>();
+    player.stop();
+}
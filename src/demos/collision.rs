@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::physics::{self, Ccd, Collider, CollisionStarted, Mass, Restitution, Velocity, WallHit, WindowBounds};
+use super::Demo;
+
+pub const GAMETITLE: &str = "衝突判定";
+const BALL_COUNT: usize = 9;
+const BALL_SIZE: f32 = 20.0;
+const BALL_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const BALL_MARGIN: f32 = 10.0;
+const BALL_SPEED: f32 = 80.0;
+const BALL_MASS: f32 = 1.0;
+const BALL_RESTITUTION: f32 = 1.0;
+
+/// ボール同士が衝突するサンプルのプラグイン
+pub struct CollisionDemoPlugin;
+
+impl Plugin for CollisionDemoPlugin {
+    fn build(&self, app: &mut App) {
+        physics::add_physics(app);
+        app
+            .add_systems(OnEnter(Demo::Collision), setup)
+        ;
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Ball;
+
+/// 衝突判定を実装するためのセットアップ
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    info_once!("setup");
+
+    // カメラを生成
+    commands.spawn((Camera2d, StateScoped(Demo::Collision)));
+
+    // 画面端の壁を生成
+    commands.spawn((WindowBounds, StateScoped(Demo::Collision)));
+
+    // 任意の数のボールを生成
+    let shape = meshes.add(Circle::new(BALL_SIZE));
+    let color = materials.add(BALL_COLOR);
+    let init_x = (-BALL_SIZE * 2.0 - BALL_MARGIN) * (BALL_COUNT / 2) as f32;
+    for i in 0..BALL_COUNT {
+        let x = init_x + ((BALL_SIZE * 2.0 + BALL_MARGIN) * i as f32);
+        let translation = Vec3::ZERO.with_x(x);
+        let x_speed = if i == 0 { BALL_SPEED } else { 0.0 };
+        commands.spawn((
+            Mesh2d(shape.clone()),
+            MeshMaterial2d(color.clone()),
+            Transform::from_translation(translation),
+            Ball,
+            Collider::circle(BALL_SIZE),
+            Mass(BALL_MASS),
+            Restitution(BALL_RESTITUTION),
+            Ccd,
+            Velocity(Vec2::ZERO.with_x(x_speed)),
+            StateScoped(Demo::Collision),
+        ))
+        .observe(log_collision)
+        .observe(log_wall_hit);
+    }
+}
+
+/// ボール同士が触れ始めたことをログに出す関数
+fn log_collision(trigger: Trigger<CollisionStarted>) {
+    let entity = trigger.target();
+    debug!("ball collision! {entity} -> {}", trigger.other(entity));
+}
+
+/// 壁に当たったことをログに出す関数
+fn log_wall_hit(trigger: Trigger<WallHit>) {
+    debug!("wall collision! {:?}", trigger.side);
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+pub mod app_state;
+pub mod audio;
+pub mod collision;
+pub mod scoreboard;
+pub mod setup;
+pub mod spritesheet;
+pub mod ui;
+pub mod velocity;
+
+/// 起動中のサンプルを管理するステート
+/// サンプルが生成したエンティティは`StateScoped`で終了時に削除される
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[states(scoped_entities)]
+pub enum Demo {
+    #[default]
+    Menu,
+    Velocity,
+    Collision,
+    Scoreboard,
+    Audio,
+    Spritesheet,
+    Ui,
+    AppState,
+    Setup,
+}
+
+impl Demo {
+    /// ランチャーに表示するサンプルの一覧
+    pub const LIST: [Demo; 8] = [
+        Demo::Velocity,
+        Demo::Collision,
+        Demo::Scoreboard,
+        Demo::Audio,
+        Demo::Spritesheet,
+        Demo::Ui,
+        Demo::AppState,
+        Demo::Setup,
+    ];
+
+    /// サンプルのタイトルを返す関数
+    pub fn title(&self) -> &'static str {
+        match self {
+            Demo::Menu => crate::launcher::GAMETITLE,
+            Demo::Velocity => velocity::GAMETITLE,
+            Demo::Collision => collision::GAMETITLE,
+            Demo::Scoreboard => scoreboard::GAMETITLE,
+            Demo::Audio => audio::GAMETITLE,
+            Demo::Spritesheet => spritesheet::GAMETITLE,
+            Demo::Ui => ui::GAMETITLE,
+            Demo::AppState => app_state::GAMETITLE,
+            Demo::Setup => setup::GAMETITLE,
+        }
+    }
+}
+
+/// 全てのサンプルを登録するプラグイン
+pub struct DemosPlugin;
+
+impl Plugin for DemosPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            velocity::VelocityDemoPlugin,
+            collision::CollisionDemoPlugin,
+            scoreboard::ScoreboardDemoPlugin,
+            audio::AudioDemoPlugin,
+            spritesheet::SpritesheetDemoPlugin,
+            ui::UiDemoPlugin,
+            app_state::AppStateDemoPlugin,
+            setup::SetupDemoPlugin,
+        ));
+    }
+}
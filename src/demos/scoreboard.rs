@@ -0,0 +1,445 @@
+use bevy::{
+    prelude::*,
+    color::palettes::css,
+    input::{ButtonState, keyboard::{Key, KeyboardInput}},
+    time::Stopwatch,
+    window::PrimaryWindow,
+};
+
+use crate::score::{
+    HighScoreEntry,
+    HighScores,
+    RollingScore,
+    Score,
+    ScoreEvent,
+    ScoreFormat,
+    ScoreMultiplier,
+    ScorePopup,
+    ScorePopupStyle,
+    ScoreRules,
+    ScoreSet,
+    ScoreSource,
+    Scoreboard,
+    ScreenEdge,
+    add_score,
+    high_scores::MAX_NAME_LENGTH,
+    rules::PATH_SCORE_RULES,
+};
+use super::Demo;
+
+pub const GAMETITLE: &str = "スコアボード";
+const PATH_FONT: &str = "fonts/misaki_gothic.ttf";
+const DESCRIPTION_TEXT: &str = "Aキー（1P）かLキー（2P）かクリックでスコアが大きくなる\nKキーでライフが減り、Enterキーでラウンドを終える";
+const COMBO_TEXT: &str = "コンボ：";
+const HIGH_SCORES_TITLE: &str = "ハイスコア";
+const NAME_PROMPT_TEXT: &str = "ハイスコア！なまえを入力してEnter：";
+const DEFAULT_NAME: &str = "???";
+const TEXT_SIZE: f32 = 30.0;
+const LIST_TEXT_SIZE: f32 = 16.0;
+const TEXT_PADDING: f32 = 15.0;
+const POPUP_TEXT_SIZE: f32 = 20.0;
+const POPUP_COLOR: Srgba = css::GOLD;
+/// Aキーで得点したときにポップアップを表示する位置
+const PLAYER_ONE_POPUP_POSITION: Vec2 = Vec2::new(-100.0, 60.0);
+/// Lキーで得点したときにポップアップを表示する位置
+const PLAYER_TWO_POPUP_POSITION: Vec2 = Vec2::new(100.0, 60.0);
+pub const PLAYER_ONE: &str = "1P";
+pub const PLAYER_TWO: &str = "2P";
+pub const LIVES: &str = "ライフ";
+pub const TIME: &str = "タイム";
+pub const MAX_LIVES: usize = 3;
+pub const KEY_ADD_SCORE: KeyCode = KeyCode::KeyA;
+pub const KEY_ADD_SCORE_PLAYER_TWO: KeyCode = KeyCode::KeyL;
+pub const KEY_LOSE_LIFE: KeyCode = KeyCode::KeyK;
+pub const KEY_FINISH_ROUND: KeyCode = KeyCode::Enter;
+pub const MOUSE_ADD_SCORE: MouseButton = MouseButton::Left;
+
+/// キーを押すとプレイヤーごとのスコアが加算され、ラウンドの終わりにハイスコアを記録するサンプルのプラグイン
+/// スコアのほかに、ライフと経過時間のスコアボードを画面の端に並べる
+pub struct ScoreboardDemoPlugin;
+
+impl Plugin for ScoreboardDemoPlugin {
+    fn build(&self, app: &mut App) {
+        add_score(app);
+        app
+            .init_resource::<HighScores>()
+            .add_systems(OnEnter(Demo::Scoreboard), setup)
+            .add_systems(OnExit(Demo::Scoreboard), exit)
+            .add_systems(Update, (
+                enter_name,
+                update,
+                lose_life,
+                tick_round_time,
+                finish_round,
+                update_high_score_list,
+                update_name_prompt,
+                update_combo_text,
+            ).chain().before(ScoreSet).run_if(in_state(Demo::Scoreboard)))
+        ;
+    }
+}
+
+/// プレイヤーのスコアボードと、得点するキーとポップアップの位置
+#[derive(Component, Debug)]
+pub struct PlayerInput {
+    pub key: KeyCode,
+    pub popup_position: Vec2,
+}
+
+/// ライフのスコアボードの目印
+#[derive(Component, Debug)]
+pub struct Lives;
+
+/// ラウンドの経過時間を秒数で表示するスコアボード
+#[derive(Component, Debug, Default, Deref, DerefMut)]
+pub struct RoundTime(Stopwatch);
+
+/// スコアボードのコンボ数と倍率を表示するテキスト
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct ComboText(pub String);
+
+/// 読み込んだ得点のルールのハンドルを保持するリソース
+#[derive(Resource, Deref, DerefMut)]
+struct ScoreRulesHandle(Handle<ScoreRules>);
+
+/// ハイスコアの一覧を表示するテキストの目印
+#[derive(Component, Debug)]
+pub struct HighScoreList;
+
+/// なまえの入力欄を表示するテキストの目印
+#[derive(Component, Debug)]
+struct NamePrompt;
+
+/// ハイスコアを出したときに、なまえの入力中の状態を管理するリソース
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct NameEntry {
+    pub name: String,
+    pub score: usize,
+}
+
+/// スコアボードのセットアップを行う関数
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // カメラと得点のルールを準備
+    commands.spawn((Camera2d, StateScoped(Demo::Scoreboard)));
+    commands.insert_resource(ScoreRulesHandle(asset_server.load(PATH_SCORE_RULES)));
+    let font = asset_server.load(PATH_FONT);
+    commands.insert_resource(ScorePopupStyle {
+        font: TextFont {
+            font: font.clone(),
+            font_size: POPUP_TEXT_SIZE,
+            ..Default::default()
+        },
+        color: POPUP_COLOR.into(),
+    });
+
+    // 説明を追加
+    commands.spawn((
+        Text2d::new(DESCRIPTION_TEXT),
+        TextFont::from_font(font.clone()),
+        StateScoped(Demo::Scoreboard),
+    ));
+
+    let text_font = TextFont {
+        font: font.clone(),
+        font_size: TEXT_SIZE,
+        ..Default::default()
+    };
+    let list_font = TextFont {
+        font,
+        font_size: LIST_TEXT_SIZE,
+        ..Default::default()
+    };
+
+    // 左上に1Pのスコアとコンボ
+    commands.spawn((
+        ScreenEdge::TopLeft.node(TEXT_PADDING),
+        StateScoped(Demo::Scoreboard),
+        children![
+            scoreboard_text(PLAYER_ONE, &text_font, (
+                Score(0),
+                Scoreboard::new(PLAYER_ONE).with_sources([ScoreSource::Input]),
+                PlayerInput { key: KEY_ADD_SCORE, popup_position: PLAYER_ONE_POPUP_POSITION },
+                RollingScore::new(TEXT_SIZE),
+            )),
+            (Text::default(), list_font.clone(), ComboText(PLAYER_ONE.to_string())),
+        ],
+    ));
+
+    // 右上に2Pのスコア（全角の数字と区切り文字）とコンボ、ハイスコアの一覧
+    commands.spawn((
+        ScreenEdge::TopRight.node(TEXT_PADDING),
+        StateScoped(Demo::Scoreboard),
+        children![
+            scoreboard_text(PLAYER_TWO, &text_font, (
+                Score(0),
+                Scoreboard::new(PLAYER_TWO),
+                PlayerInput { key: KEY_ADD_SCORE_PLAYER_TWO, popup_position: PLAYER_TWO_POPUP_POSITION },
+                RollingScore::new(TEXT_SIZE),
+                ScoreFormat::default().with_separator(',').full_width(),
+            )),
+            (Text::default(), list_font.clone(), ComboText(PLAYER_TWO.to_string())),
+            (Text::default(), list_font.clone(), HighScoreList),
+        ],
+    ));
+
+    // 上の中央に経過時間（分:秒）
+    commands.spawn((
+        ScreenEdge::Top.node(TEXT_PADDING),
+        StateScoped(Demo::Scoreboard),
+        children![
+            scoreboard_text(TIME, &text_font, (
+                Score(0),
+                Scoreboard::new(TIME),
+                RoundTime::default(),
+                ScoreFormat::time(),
+            )),
+        ],
+    ));
+
+    // 右下にライフ（0で埋めた全角の数字）
+    commands.spawn((
+        ScreenEdge::BottomRight.node(TEXT_PADDING),
+        StateScoped(Demo::Scoreboard),
+        children![
+            scoreboard_text(LIVES, &text_font, (
+                Score(MAX_LIVES),
+                Scoreboard::new(LIVES),
+                Lives,
+                ScoreFormat::default().zero_padded(2).full_width(),
+            )),
+        ],
+    ));
+
+    // 左下になまえの入力欄
+    commands.spawn((
+        ScreenEdge::BottomLeft.node(TEXT_PADDING),
+        StateScoped(Demo::Scoreboard),
+        children![(Text::default(), list_font, NamePrompt)],
+    ));
+}
+
+/// 名前の後ろにスコアを表示するテキストを返す関数
+fn scoreboard_text(name: &str, font: &TextFont, board: impl Bundle) -> impl Bundle {
+    (
+        Text::new(format!("{name}：")),
+        font.clone(),
+        children![(TextSpan::default(), font.clone(), board)],
+    )
+}
+
+/// スコアボードの更新を行う関数
+fn update(
+    mut events: EventWriter<ScoreEvent>,
+    player_query: Query<(&Scoreboard, &PlayerInput)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    name_entry: Option<Res<NameEntry>>,
+) {
+    // なまえの入力中は、文字として扱う
+    if name_entry.is_some() {
+        return;
+    }
+    // プレイヤーごとのキーが押されたら、そのプレイヤーのスコアを加算する
+    for (board, input) in &player_query {
+        if keyboard_input.just_pressed(input.key) {
+            events.write(ScoreEvent::new(1, ScoreSource::Input).to(&board.name).at(input.popup_position));
+        }
+    }
+    // クリックされたらスコアを加算し、クリックした位置にポップアップを表示する
+    if mouse_input.just_pressed(MOUSE_ADD_SCORE) {
+        let cursor = window_query.single().ok()
+            .and_then(|window| window.cursor_position())
+            .zip(camera_query.single().ok())
+            .and_then(|(cursor, (camera, transform))| camera.viewport_to_world_2d(transform, cursor).ok());
+        let event = ScoreEvent::new(1, ScoreSource::Input);
+        events.write(match cursor {
+            Some(position) => event.at(position),
+            None => event,
+        });
+    }
+}
+
+/// Kキーが押されたらライフを減らす関数
+fn lose_life(
+    mut query: Query<&mut Score, With<Lives>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    name_entry: Option<Res<NameEntry>>,
+) {
+    if name_entry.is_some() || !keyboard_input.just_pressed(KEY_LOSE_LIFE) {
+        return;
+    }
+    for mut lives in &mut query {
+        **lives = lives.saturating_sub(1);
+        debug!("lives: {}", **lives);
+    }
+}
+
+/// ラウンドの経過時間を進め、秒数をスコアボードに表示する関数
+/// なまえの入力中は止める
+fn tick_round_time(
+    mut query: Query<(&mut Score, &mut RoundTime)>,
+    time: Res<Time>,
+    name_entry: Option<Res<NameEntry>>,
+) {
+    info_once!("tick_round_time");
+
+    if name_entry.is_some() {
+        return;
+    }
+    for (mut score, mut round_time) in &mut query {
+        round_time.tick(time.delta());
+        score.set_if_neq(Score(round_time.elapsed_secs() as usize));
+    }
+}
+
+/// ラウンドの終わりに元に戻すスコアボード
+type RoundBoard = (
+    &'static mut Score,
+    &'static mut ScoreMultiplier,
+    Has<PlayerInput>,
+    Has<Lives>,
+    Option<&'static mut RoundTime>,
+);
+
+/// Enterキーを押すかライフが無くなるとラウンドを終え、ハイスコアならなまえの入力を始める関数
+/// なまえを入力するのは、最も高いプレイヤーのスコア
+fn finish_round(
+    mut commands: Commands,
+    mut query: Query<RoundBoard>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    high_scores: Res<HighScores>,
+    name_entry: Option<Res<NameEntry>>,
+) {
+    info_once!("finish_round");
+
+    let out_of_lives = query.iter().any(|(lives, _, _, is_lives, _)| is_lives && **lives == 0);
+    if name_entry.is_some() || !(keyboard_input.just_pressed(KEY_FINISH_ROUND) || out_of_lives) {
+        return;
+    }
+
+    let best = query.iter()
+        .filter(|(_, _, is_player, ..)| *is_player)
+        .map(|(score, ..)| **score)
+        .max()
+        .unwrap_or_default();
+    if high_scores.qualifies(best) {
+        debug!("new high score: {best}");
+        commands.insert_resource(NameEntry { name: String::new(), score: best });
+    }
+
+    // 全てのスコアボードとコンボを元に戻す
+    for (mut score, mut multiplier, _, is_lives, round_time) in &mut query {
+        **score = if is_lives { MAX_LIVES } else { 0 };
+        multiplier.reset();
+        if let Some(mut round_time) = round_time {
+            round_time.reset();
+        }
+    }
+}
+
+/// 入力された文字でなまえを編集し、Enterキーでハイスコアに記録する関数
+/// 入力中でなくてもイベントを読み進め、ラウンドを終えたEnterキーで確定しないようにする
+fn enter_name(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut name_entry: Option<ResMut<NameEntry>>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    info_once!("enter_name");
+
+    for event in keyboard_events.read() {
+        let Some(entry) = name_entry.as_deref_mut() else {
+            continue;
+        };
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                let name = if entry.name.is_empty() { DEFAULT_NAME.to_string() } else { entry.name.clone() };
+                high_scores.insert(HighScoreEntry::new(name, entry.score));
+                if let Err(error) = high_scores.save() {
+                    warn!("{error}");
+                }
+                commands.remove_resource::<NameEntry>();
+                name_entry = None;
+            }
+            Key::Backspace => {
+                entry.name.pop();
+            }
+            Key::Character(text) => {
+                for character in text.chars().filter(|c| !c.is_control()) {
+                    if entry.name.chars().count() < MAX_NAME_LENGTH {
+                        entry.name.push(character);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// ハイスコアが変わったときと一覧が生成されたときに、一覧の表示を更新する関数
+fn update_high_score_list(
+    mut query: Query<&mut Text, With<HighScoreList>>,
+    added: Query<(), Added<HighScoreList>>,
+    high_scores: Res<HighScores>,
+) {
+    info_once!("update_high_score_list");
+
+    if !high_scores.is_changed() && added.is_empty() {
+        return;
+    }
+
+    let lines: Vec<String> = high_scores.entries().iter()
+        .enumerate()
+        .map(|(rank, entry)| format!("{:>2}. {:<8} {:>6} {}", rank + 1, entry.name, entry.score, entry.date))
+        .collect();
+    for mut text in &mut query {
+        **text = format!("{HIGH_SCORES_TITLE}\n{}", lines.join("\n"));
+    }
+}
+
+/// なまえの入力欄の表示を更新する関数
+fn update_name_prompt(
+    mut query: Query<&mut Text, With<NamePrompt>>,
+    name_entry: Option<Res<NameEntry>>,
+) {
+    let prompt = name_entry.map_or(String::new(), |entry| format!("{NAME_PROMPT_TEXT}{}_", entry.name));
+    for mut text in &mut query {
+        text.set_if_neq(Text(prompt.clone()));
+    }
+}
+
+/// スコアボードごとのコンボ数と倍率の表示を更新する関数
+fn update_combo_text(
+    mut query: Query<(&mut Text, &ComboText)>,
+    board_query: Query<(&Scoreboard, &ScoreMultiplier)>,
+    rules: Res<ScoreRules>,
+) {
+    for (mut text, ComboText(name)) in &mut query {
+        let Some((_, multiplier)) = board_query.iter().find(|(board, _)| board.name == *name) else {
+            continue;
+        };
+        let combo = format!("{COMBO_TEXT}{} ×{}", multiplier.combo, multiplier.multiplier(&rules));
+        text.set_if_neq(Text(combo));
+    }
+}
+
+/// 入力中のなまえと、ポップアップを破棄する関数
+fn exit(
+    mut commands: Commands,
+    popup_query: Query<Entity, With<ScorePopup>>,
+) {
+    for entity in &popup_query {
+        commands.entity(entity).despawn();
+    }
+    commands.insert_resource(ScorePopupStyle::default());
+    commands.remove_resource::<NameEntry>();
+    commands.remove_resource::<ScoreRulesHandle>();
+}
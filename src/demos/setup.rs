@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::{
+    FIXED_TIMESTEP,
+    config::{GameConfig, GameConfigPlugin},
+};
+use super::Demo;
+
+pub const GAMETITLE: &str = "セットアップ";
+pub const BACKGROUND_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+pub const PATH_CONFIG: &str = "config/setup.config.ron";
+
+/// 読み込んだ設定ファイルのハンドルを保持するリソース
+#[derive(Resource, Deref, DerefMut)]
+struct GameConfigHandle(Handle<GameConfig>);
+
+/// 新しいゲームを作るときの雛形となるサンプルのプラグイン
+/// タイトルや背景色などは設定ファイルから読み込まれ、ファイルを書き換えると反映される
+pub struct SetupDemoPlugin;
+
+impl Plugin for SetupDemoPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(GameConfigPlugin)
+            .add_systems(OnEnter(Demo::Setup), setup)
+            .add_systems(OnExit(Demo::Setup), exit)
+        ;
+    }
+}
+
+/// 設定ファイルとカメラをセットアップする関数
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    info_once!("setup");
+
+    commands.insert_resource(ClearColor(BACKGROUND_COLOR));
+    commands.insert_resource(GameConfigHandle(asset_server.load(PATH_CONFIG)));
+    commands.spawn((Camera2d, StateScoped(Demo::Setup)));
+}
+
+/// 設定ファイルで変更した背景色と固定タイムステップを元に戻す関数
+fn exit(
+    mut commands: Commands,
+    mut time: ResMut<Time<Fixed>>,
+) {
+    info_once!("exit");
+
+    commands.remove_resource::<GameConfigHandle>();
+    commands.insert_resource(ClearColor::default());
+    time.set_timestep_seconds(FIXED_TIMESTEP);
+}
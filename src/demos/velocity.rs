@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use crate::physics::{self, Collider, Velocity, WallHit, WindowBounds};
+use super::Demo;
+
+pub const GAMETITLE: &str = "速度";
+const SQUARE_SIZE: f32 = 40.0;
+const SQUARE_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const SQUARE_SPEED: f32 = 80.0;
+
+/// 四角形が画面端で跳ね返るサンプルのプラグイン
+pub struct VelocityDemoPlugin;
+
+impl Plugin for VelocityDemoPlugin {
+    fn build(&self, app: &mut App) {
+        physics::add_physics(app);
+        app
+            .add_systems(OnEnter(Demo::Velocity), setup)
+        ;
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    info_once!("setup");
+
+    // カメラを生成
+    commands.spawn((Camera2d, StateScoped(Demo::Velocity)));
+
+    // 画面端の壁を生成
+    commands.spawn((WindowBounds, StateScoped(Demo::Velocity)));
+
+    // 四角形を生成
+    let shape = meshes.add(Rectangle::new(SQUARE_SIZE, SQUARE_SIZE));
+    let color = materials.add(SQUARE_COLOR);
+    commands.spawn((
+        Mesh2d(shape),
+        MeshMaterial2d(color),
+        Velocity(Vec2::new(SQUARE_SPEED, SQUARE_SPEED)),
+        Collider::rectangle(SQUARE_SIZE, SQUARE_SIZE),
+        StateScoped(Demo::Velocity),
+    )).observe(log_wall_hit);
+}
+
+/// 壁に当たったことをログに出す関数
+fn log_wall_hit(trigger: Trigger<WallHit>) {
+    debug!("wall collision! {:?}", trigger.side);
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use crate::demos::{Demo, DemosPlugin};
+
+pub const GAMETITLE: &str = "サンプル一覧";
+const PATH_FONT: &str = "fonts/misaki_gothic.ttf";
+const TEXT_SIZE: f32 = 24.0;
+const TEXT_PADDING: f32 = 15.0;
+const KEY_BACK_TO_MENU: KeyCode = KeyCode::Escape;
+const KEY_DEMOS: [KeyCode; 8] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+];
+
+/// サンプルを選んで起動するランチャーのプラグイン
+/// 数字キーでサンプルを起動し、Escキーで一覧に戻る
+pub struct LauncherPlugin;
+
+impl Plugin for LauncherPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_state::<Demo>()
+            .add_plugins(DemosPlugin)
+            .add_systems(OnEnter(Demo::Menu), menu_setup)
+            .add_systems(Update, (
+                menu_update.run_if(in_state(Demo::Menu)),
+                back_to_menu.run_if(not(in_state(Demo::Menu))),
+                update_window_title.run_if(state_changed::<Demo>),
+            ))
+        ;
+    }
+}
+
+/// サンプルの一覧を表示する関数
+fn menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    info_once!("menu_setup");
+
+    // カメラを生成
+    commands.spawn((Camera2d, StateScoped(Demo::Menu)));
+
+    // サンプルの一覧を生成
+    let mut text = format!("{}\n\n", GAMETITLE);
+    for (key, demo) in KEY_DEMOS.iter().zip(Demo::LIST) {
+        text += &format!("{:?}: {}\n", key, demo.title());
+    }
+    text += &format!("\n{:?}: {}", KEY_BACK_TO_MENU, GAMETITLE);
+    commands.spawn((
+        Text::new(text),
+        TextFont {
+            font: asset_server.load(PATH_FONT),
+            font_size: TEXT_SIZE,
+            ..Default::default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(TEXT_PADDING),
+            left: Val::Px(TEXT_PADDING),
+            ..Default::default()
+        },
+        StateScoped(Demo::Menu),
+    ));
+}
+
+/// 数字キーが押されたら対応するサンプルを起動する関数
+fn menu_update(
+    mut next_state: ResMut<NextState<Demo>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    info_once!("menu_update");
+
+    for (key, demo) in KEY_DEMOS.iter().zip(Demo::LIST) {
+        if keyboard_input.just_pressed(*key) {
+            debug!("launch {:?}", demo);
+            next_state.set(demo);
+        }
+    }
+}
+
+/// Escキーが押されたらサンプルを終了して一覧に戻る関数
+fn back_to_menu(
+    mut next_state: ResMut<NextState<Demo>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    info_once!("back_to_menu");
+
+    if keyboard_input.just_pressed(KEY_BACK_TO_MENU) {
+        next_state.set(Demo::Menu);
+    }
+}
+
+/// 起動中のサンプルに合わせてウィンドウのタイトルを変更する関数
+fn update_window_title(
+    mut window_query: Query<&mut Window>,
+    state: Res<State<Demo>>,
+) {
+    info_once!("update_window_title");
+
+    for mut window in &mut window_query {
+        window.title = state.title().to_string();
+    }
+}
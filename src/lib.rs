@@ -0,0 +1,215 @@
+use std::{env, num::ParseIntError, path::PathBuf, time::Duration};
+
+use bevy::{
+    prelude::*,
+    log::{LogPlugin, tracing::dispatcher},
+    asset::AssetMetaCheck,
+    app::ScheduleRunnerPlugin,
+    audio::AudioLoader,
+    image::{CompressedImageFormats, ImageLoader},
+    input::InputPlugin,
+    state::app::StatesPlugin,
+    text::FontLoader,
+    time::TimeUpdateStrategy,
+};
+
+pub mod audio;
+pub mod config;
+pub mod demos;
+pub mod launcher;
+pub mod physics;
+pub mod score;
+pub mod slider;
+pub mod storage;
+
+pub const WINDOW_SIZE: Vec2 = Vec2::new(640.0, 480.0);
+pub const LOG_FILTER: &str = "info,wgpu_core=warn,wgpu_hal=warn,example_bevy=debug";
+pub const FIXED_TIMESTEP: f64 = 1.0 / 60.0;
+pub const HEADLESS_ARG: &str = "--headless";
+pub const HEADLESS_ENV: &str = "EXAMPLE_HEADLESS";
+pub const HEADLESS_TICKS: u32 = 600;
+
+/// 全てのサンプルで共通の設定を行うプラグイン
+/// - ウィンドウのサイズとタイトル
+/// - ログのフィルター
+/// - 画像のサンプリング（ニアレストネイバー）
+/// - アセットのメタファイルを確認しない
+/// - 60Hzの固定タイムステップ
+///
+/// `--headless[=N]`引数か`EXAMPLE_HEADLESS=N`環境変数を指定すると、
+/// ウィンドウを作らずにNティック（既定は600）だけ実行して終了する
+/// Nが数値でない場合は、起動時にエラーを表示して既定のティック数で実行する
+pub struct ExampleBasePlugin {
+    pub title: String,
+    pub window_size: Vec2,
+    pub log_filter: String,
+    pub fixed_timestep: f64,
+    pub headless: Option<u32>,
+    /// 読み取れなかったヘッドレスモードのティック数のエラー（`build`でログに出す）
+    pub headless_error: Option<String>,
+    /// データと設定の保存先（無ければプラットフォームのフォルダ）
+    pub storage_root: Option<PathBuf>,
+}
+
+impl ExampleBasePlugin {
+    /// ウィンドウのタイトルを指定してプラグインを作成する関数
+    pub fn new(title: &str) -> Self {
+        let (headless, headless_error) = match headless_ticks() {
+            Ok(ticks) => (ticks, None),
+            Err(error) => (Some(HEADLESS_TICKS), Some(error)),
+        };
+        Self {
+            title: title.to_string(),
+            window_size: WINDOW_SIZE,
+            log_filter: LOG_FILTER.to_string(),
+            fixed_timestep: FIXED_TIMESTEP,
+            headless,
+            headless_error,
+            storage_root: None,
+        }
+    }
+
+    /// 指定したティック数だけヘッドレスモードで実行するように変更する関数
+    /// 引数や環境変数のティック数が不正だったエラーは、使わないので消す
+    pub fn headless(mut self, ticks: u32) -> Self {
+        self.headless = Some(ticks);
+        self.headless_error = None;
+        self
+    }
+
+    /// データと設定をプラットフォームのフォルダの代わりに、指定したフォルダに保存するように変更する関数
+    pub fn storage_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.storage_root = Some(root.into());
+        self
+    }
+
+    /// ウィンドウを使うプラグインを追加する関数
+    fn build_window(&self, app: &mut App) {
+        app.add_plugins(DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: self.window_size.into(),
+                    title: self.title.clone(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .set(ImagePlugin::default_nearest())
+            .set(LogPlugin {
+                filter: self.log_filter.clone(),
+                level: bevy::log::Level::DEBUG,
+                ..Default::default()
+            })
+            .set(AssetPlugin {
+                meta_check: AssetMetaCheck::Never,
+                ..Default::default()
+            })
+        );
+    }
+
+    /// ウィンドウやGPUを使わずにサンプルのシステムを動かすプラグインを追加する関数
+    /// 1回の更新で固定タイムステップ1回分だけ時間を進める
+    fn build_headless(&self, app: &mut App, ticks: u32) {
+        app
+            .add_plugins((
+                MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)),
+                TransformPlugin,
+                InputPlugin,
+                StatesPlugin,
+                AssetPlugin {
+                    meta_check: AssetMetaCheck::Never,
+                    ..Default::default()
+                },
+            ))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(
+                Duration::from_secs_f64(self.fixed_timestep),
+            ))
+            .init_resource::<ClearColor>()
+            // サンプルが読み込むアセットを登録
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<Font>()
+            .init_asset::<AudioSource>()
+            .register_asset_loader(ImageLoader::new(CompressedImageFormats::NONE))
+            .init_asset_loader::<FontLoader>()
+            .init_asset_loader::<AudioLoader>()
+            // 指定したティック数が経過したら終了
+            .insert_resource(HeadlessTicks(ticks))
+            .add_systems(Last, exit_after_ticks)
+        ;
+
+        // テストのように1つのプロセスで複数のアプリを作る場合は、最初の1回だけロガーを設定する
+        if !dispatcher::has_been_set() {
+            app.add_plugins(LogPlugin {
+                filter: self.log_filter.clone(),
+                level: bevy::log::Level::DEBUG,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+impl Plugin for ExampleBasePlugin {
+    fn build(&self, app: &mut App) {
+        match self.headless {
+            Some(ticks) => self.build_headless(app, ticks),
+            None => self.build_window(app),
+        }
+        // ロガーを設定した後でないと表示されないため、ここでエラーを出す
+        if let Some(error) = &self.headless_error {
+            error!("{error}; running {} ticks instead", self.headless.unwrap_or(HEADLESS_TICKS));
+        }
+        app.insert_resource(Time::<Fixed>::from_seconds(self.fixed_timestep));
+        if let Some(root) = &self.storage_root {
+            app.insert_resource(storage::StorageRoot(root.clone()));
+        }
+    }
+}
+
+/// ヘッドレスモードの残りティック数を管理するリソース
+#[derive(Resource, Debug, Deref, DerefMut)]
+struct HeadlessTicks(u32);
+
+/// コマンドライン引数か環境変数からヘッドレスモードのティック数を取得する関数
+/// 数値が不正な場合は、値と理由を書いたエラーを返す
+fn headless_ticks() -> Result<Option<u32>, String> {
+    let Some(value) = env::args()
+        .find_map(|arg| match arg.strip_prefix(HEADLESS_ARG) {
+            Some("") => Some(String::new()),
+            Some(rest) => rest.strip_prefix('=').map(str::to_string),
+            None => None,
+        })
+        .or_else(|| env::var(HEADLESS_ENV).ok())
+    else {
+        return Ok(None);
+    };
+    parse_headless_ticks(&value)
+        .map(Some)
+        .map_err(|error| format!("invalid headless tick count {value:?} ({HEADLESS_ARG}=N or {HEADLESS_ENV}=N): {error}"))
+}
+
+/// ヘッドレスモードのティック数を読み取る関数
+/// 空の場合は既定のティック数を返す
+pub fn parse_headless_ticks(value: &str) -> Result<u32, ParseIntError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(HEADLESS_TICKS);
+    }
+    value.parse()
+}
+
+/// 指定したティック数が経過したらアプリを終了する関数
+fn exit_after_ticks(
+    mut ticks: ResMut<HeadlessTicks>,
+    mut exit: EventWriter<AppExit>,
+) {
+    info_once!("exit_after_ticks");
+
+    **ticks = ticks.saturating_sub(1);
+    if **ticks == 0 {
+        debug!("headless run finished");
+        exit.write(AppExit::Success);
+    }
+}
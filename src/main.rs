@@ -1,3 +1,14 @@
+use bevy::prelude::*;
+
+use example_bevy::{
+    ExampleBasePlugin,
+    launcher::{self, LauncherPlugin},
+};
+
+/// サンプルの一覧から選んだものを1つのプロセスで切り替えながら起動する
 fn main() {
-    println!("Hello World");
+    App::new()
+        .add_plugins(ExampleBasePlugin::new(launcher::GAMETITLE))
+        .add_plugins(LauncherPlugin)
+        .run();
 }
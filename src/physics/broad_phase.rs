@@ -0,0 +1,135 @@
+use bevy::{
+    prelude::*,
+    math::bounding::{Aabb2d, IntersectsVolume},
+    platform::collections::HashMap,
+};
+
+use super::CollisionLayers;
+
+/// 空間ハッシュのセルの大きさの既定値
+pub const CELL_SIZE: f32 = 64.0;
+
+/// 一様グリッドの空間ハッシュで、接触しそうな形状の組を絞り込むリソース（広域判定）
+/// 固定タイムステップごとに作り直され、境界ボックスが重なり衝突するグループ同士の組を狭域判定に渡す
+#[derive(Resource, Debug)]
+pub struct BroadPhase {
+    /// セルの大きさ
+    /// 形状の大きさと同じくらいにすると、判定する組が少なくなる
+    pub cell_size: f32,
+    entries: Vec<(Entity, Aabb2d)>,
+    layers: Vec<CollisionLayers>,
+    cells: HashMap<IVec2, Vec<usize>>,
+    pairs: Vec<(Entity, Entity)>,
+}
+
+impl Default for BroadPhase {
+    fn default() -> Self {
+        Self::new(CELL_SIZE)
+    }
+}
+
+impl BroadPhase {
+    /// セルの大きさを指定して空の空間ハッシュを作成する関数
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            entries: Vec::new(),
+            layers: Vec::new(),
+            cells: HashMap::default(),
+            pairs: Vec::new(),
+        }
+    }
+
+    /// 登録された形状と組を全て削除する関数
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.layers.clear();
+        self.cells.clear();
+        self.pairs.clear();
+    }
+
+    /// 形状の境界ボックスと衝突判定のグループを登録する関数
+    pub fn insert(&mut self, entity: Entity, aabb: Aabb2d, layers: CollisionLayers) {
+        let index = self.entries.len();
+        self.entries.push((entity, aabb));
+        self.layers.push(layers);
+        let (min, max) = self.cell_range(&aabb);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    /// 境界ボックスが重なり、衝突するグループ同士の組を求める関数
+    /// 組は登録した順に並び、1つ目は2つ目より先に登録された形状になる
+    pub fn update_pairs(&mut self) {
+        self.pairs.clear();
+        let mut pairs = Vec::new();
+        for (cell, indices) in &self.cells {
+            for (i, &index1) in indices.iter().enumerate() {
+                let aabb1 = self.entries[index1].1;
+                for &index2 in &indices[i + 1..] {
+                    let aabb2 = self.entries[index2].1;
+                    if !aabb1.intersects(&aabb2)
+                    || !self.layers[index1].interacts_with(&self.layers[index2]) {
+                        continue;
+                    }
+                    // 複数のセルにまたがる組は、重なった範囲の左下のセルだけで数える
+                    if self.cell(aabb1.min.max(aabb2.min)) != *cell {
+                        continue;
+                    }
+                    pairs.push((index1.min(index2), index1.max(index2)));
+                }
+            }
+        }
+        // ハッシュの順番に左右されないように、登録した順に並べる
+        pairs.sort_unstable();
+        self.pairs.extend(pairs.into_iter().map(|(index1, index2)| {
+            (self.entries[index1].0, self.entries[index2].0)
+        }));
+    }
+
+    /// 境界ボックスが重なり、衝突するグループ同士の組を返す関数
+    pub fn pairs(&self) -> &[(Entity, Entity)] {
+        &self.pairs
+    }
+
+    /// 境界ボックスと重なる形状と、その境界ボックスと衝突判定のグループを登録した順に返す関数
+    pub fn query_aabb(&self, aabb: &Aabb2d) -> Vec<(Entity, Aabb2d, CollisionLayers)> {
+        let (min, max) = self.cell_range(aabb);
+        let cell_count = (max.x as i64 - min.x as i64 + 1) * (max.y as i64 - min.y as i64 + 1);
+        let mut indices: Vec<usize> = if cell_count > self.entries.len() as i64 {
+            // セルの数が形状の数より多い場合は、全ての形状を調べた方が速い
+            (0..self.entries.len()).collect()
+        } else {
+            (min.y..=max.y)
+                .flat_map(|y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+                .filter_map(|cell| self.cells.get(&cell))
+                .flatten()
+                .copied()
+                .collect()
+        };
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter()
+            .filter(|&index| self.entries[index].1.intersects(aabb))
+            .map(|index| (self.entries[index].0, self.entries[index].1, self.layers[index]))
+            .collect()
+    }
+
+    /// 登録された形状と境界ボックスを返す関数
+    pub fn entries(&self) -> &[(Entity, Aabb2d)] {
+        &self.entries
+    }
+
+    /// 座標が含まれるセルを返す関数
+    fn cell(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+
+    /// 境界ボックスが含まれるセルの範囲を返す関数
+    fn cell_range(&self, aabb: &Aabb2d) -> (IVec2, IVec2) {
+        (self.cell(aabb.min), self.cell(aabb.max))
+    }
+}
@@ -0,0 +1,208 @@
+use bevy::{
+    prelude::*,
+    math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume, RayCast2d},
+};
+
+use super::{
+    Collider,
+    CollisionLayers,
+    DeterministicMode,
+    PhysicsSet,
+    Position,
+    PreviousPosition,
+    Rotation,
+    Sensor,
+    StableId,
+    collider,
+    kinematics,
+};
+
+/// 衝突した時刻より少しだけ先へ進める距離
+/// 狭域判定で接触として見つかるように、わずかにめり込ませる
+const CCD_SKIN: f32 = 0.05;
+
+/// 連続的な衝突判定を行うエンティティの目印
+/// 1ステップの移動の途中で他の形状に当たる場合は、相手と一緒に当たった時刻の位置まで戻してすり抜けを防ぐ
+/// 移動する形状は、円ならそのまま、それ以外は境界ボックスで近似する
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Ccd;
+
+/// 連続的な衝突判定を行うプラグイン
+pub struct CcdPlugin;
+
+impl Plugin for CcdPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, sweep_ccd_bodies
+            .after(kinematics::apply_velocity)
+            .in_set(PhysicsSet::Integrate));
+    }
+}
+
+/// 掃引の判定に使う、円か境界ボックスで近似した形状
+#[derive(Debug, Clone, Copy)]
+enum SweptShape {
+    Circle(BoundingCircle),
+    Aabb(Aabb2d),
+}
+
+impl SweptShape {
+    fn new(collider: &Collider, isometry: Isometry2d) -> Self {
+        match collider {
+            Collider::Circle(circle) => Self::Circle(BoundingCircle::new(isometry.translation, circle.radius)),
+            _ => Self::Aabb(collider.aabb(isometry)),
+        }
+    }
+}
+
+/// 2つの形状がそれぞれ移動するとき、最初に触れる時刻（0から1）を返す関数
+/// 最初から重なっている場合と、移動の間に触れない場合は`None`を返す
+pub fn time_of_impact(
+    collider1: &Collider,
+    isometry1: Isometry2d,
+    motion1: Vec2,
+    collider2: &Collider,
+    isometry2: Isometry2d,
+    motion2: Vec2,
+) -> Option<f32> {
+    // 2つ目の形状から見た、1つ目の形状の相対的な移動で考える
+    let motion = motion1 - motion2;
+    let length = motion.length();
+    let direction = Dir2::new(motion).ok()?;
+    let shape1 = SweptShape::new(collider1, isometry1);
+    let shape2 = SweptShape::new(collider2, isometry2);
+    let distance = match (shape1, shape2) {
+        (SweptShape::Circle(a), SweptShape::Circle(b)) => {
+            let ray = RayCast2d::new(a.center, direction, length);
+            ray.circle_intersection_at(&BoundingCircle::new(b.center, a.radius() + b.radius()))
+        }
+        (SweptShape::Circle(a), SweptShape::Aabb(b)) => {
+            ray_rounded_aabb(RayCast2d::new(a.center, direction, length), b, a.radius())
+        }
+        (SweptShape::Aabb(a), SweptShape::Circle(b)) => {
+            // 円から見て、逆向きに移動する境界ボックスとして考える
+            ray_rounded_aabb(RayCast2d::new(b.center, -direction, length), a, b.radius())
+        }
+        (SweptShape::Aabb(a), SweptShape::Aabb(b)) => {
+            let expanded = Aabb2d::new(b.center(), b.half_size() + a.half_size());
+            RayCast2d::new(a.center(), direction, length).aabb_intersection_at(&expanded)
+        }
+    }?;
+    (distance > 0.0).then_some(distance / length)
+}
+
+/// 光線が、角を丸めて広げた境界ボックスに当たる距離を返す関数
+fn ray_rounded_aabb(ray: RayCast2d, aabb: Aabb2d, radius: f32) -> Option<f32> {
+    let distance = ray.aabb_intersection_at(&aabb.grow(Vec2::splat(radius)))?;
+    let point = ray.ray.origin + *ray.ray.direction * distance;
+    let corner = point.clamp(aabb.min, aabb.max);
+    // 辺の外側で当たった場合は、角の円で当たり直す
+    if corner.x != point.x && corner.y != point.y {
+        return ray.circle_intersection_at(&BoundingCircle::new(corner, radius));
+    }
+    Some(distance)
+}
+
+/// 掃引の判定に使う形状の情報
+struct SweptBody {
+    entity: Entity,
+    id: StableId,
+    collider: Collider,
+    isometry: Isometry2d,
+    motion: Vec2,
+    bounds: Aabb2d,
+    layers: CollisionLayers,
+    sensor: bool,
+}
+
+/// 掃引の判定に使うコンポーネント
+type SweepData = (
+    Entity,
+    &'static Collider,
+    &'static mut Position,
+    &'static PreviousPosition,
+    Option<&'static Rotation>,
+    Option<&'static CollisionLayers>,
+    Has<Sensor>,
+    &'static StableId,
+);
+
+/// 連続的な衝突判定を行うエンティティと当たった相手を、移動の途中で最初に当たる位置まで戻す関数
+fn sweep_ccd_bodies(
+    mut query: Query<SweepData>,
+    ccd_query: Query<Entity, With<Ccd>>,
+    deterministic: Res<DeterministicMode>,
+) {
+    info_once!("sweep_ccd_bodies");
+
+    if ccd_query.is_empty() {
+        return;
+    }
+
+    // 移動前の位置と、移動した範囲の境界ボックスを集める
+    let mut bodies: Vec<SweptBody> = query.iter()
+        .map(|(entity, collider, position, previous, rotation, layers, sensor, id)| {
+            let isometry = collider::isometry(&Position(**previous), rotation);
+            let motion = **position - **previous;
+            let start = collider.aabb(isometry);
+            let end = Aabb2d::new(start.center() + motion, start.half_size());
+            SweptBody {
+                entity,
+                id: *id,
+                collider: *collider,
+                isometry,
+                motion,
+                bounds: start.merge(&end),
+                layers: layers.copied().unwrap_or_default(),
+                sensor,
+            }
+        })
+        .collect();
+    // 決定論的モードでは、同じ時刻に当たる相手が複数あっても、安定したIDの小さい方を選ぶ
+    if **deterministic {
+        bodies.sort_unstable_by_key(|body| body.id);
+    }
+
+    // 当たった2つの形状は、どちらも最初に当たった時刻まで戻す
+    // 相手だけ移動後の位置に残すと、相対的な移動で求めた時刻では離れてしまう
+    let mut rewinds: Vec<Option<f32>> = vec![None; bodies.len()];
+    for (index, body) in bodies.iter().enumerate() {
+        if !ccd_query.contains(body.entity) || body.sensor {
+            continue;
+        }
+        let impact = bodies.iter()
+            .enumerate()
+            .filter(|(_, other)| {
+                other.entity != body.entity
+                && !other.sensor
+                && body.layers.interacts_with(&other.layers)
+                && body.bounds.intersects(&other.bounds)
+            })
+            .filter_map(|(other_index, other)| time_of_impact(
+                &body.collider, body.isometry, body.motion,
+                &other.collider, other.isometry, other.motion,
+            ).map(|time| (other_index, time)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((other_index, time)) = impact else {
+            continue;
+        };
+        for rewind in [index, other_index] {
+            rewinds[rewind] = Some(rewinds[rewind].map_or(time, |earlier: f32| earlier.min(time)));
+        }
+    }
+
+    // 当たった位置まで戻し、狭域判定に任せる
+    for (body, time) in bodies.iter().zip(rewinds) {
+        let Some(time) = time else {
+            continue;
+        };
+        let length = body.motion.length();
+        if length == 0.0 {
+            continue;
+        }
+        let Ok((_, _, mut position, ..)) = query.get_mut(body.entity) else {
+            continue;
+        };
+        let distance = (length * time + CCD_SKIN).min(length);
+        **position = body.isometry.translation + body.motion / length * distance;
+    }
+}
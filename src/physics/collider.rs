@@ -0,0 +1,378 @@
+use bevy::{
+    prelude::*,
+    math::bounding::{Aabb2d, Bounded2d},
+};
+
+use super::{Position, Rotation};
+
+/// 計算誤差として無視する距離
+const EPSILON: f32 = 1e-5;
+/// 同じ面の頂点とみなす射影の差
+const SUPPORT_TOLERANCE: f32 = 1e-4;
+/// 多角形の形状が持てる頂点の最大数
+pub const MAX_POLYGON_VERTICES: usize = 8;
+
+/// 衝突判定に使う形状を管理するコンポーネント
+/// 位置は`Position`、回転は`Rotation`（無ければ0）を使う
+/// `Velocity`の無い形状は動かない静的な形状になり、壁や障害物に使える
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[require(Position)]
+pub enum Collider {
+    /// 円
+    Circle(Circle),
+    /// 回転しない矩形（軸並行境界ボックス）
+    Aabb(Rectangle),
+    /// 回転する矩形（有向境界ボックス）
+    Obb(Rectangle),
+    /// ローカルのY軸方向に伸びたカプセル
+    Capsule(Capsule2d),
+    /// ローカル座標の2点を結ぶ線分
+    Segment(Segment2d),
+    /// ローカル座標の頂点を反時計回りに並べた凸多角形
+    Polygon(ConvexVertices),
+}
+
+/// 凸多角形の頂点を反時計回りに並べたもの
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvexVertices {
+    points: [Vec2; MAX_POLYGON_VERTICES],
+    len: usize,
+}
+
+impl ConvexVertices {
+    /// 頂点から凸多角形を作成する関数
+    /// 時計回りの頂点は反時計回りに並べ直す
+    /// 頂点が3つ未満か最大数より多い場合と、凸でない場合は`None`を返す
+    pub fn new(vertices: &[Vec2]) -> Option<Self> {
+        let len = vertices.len();
+        if !(3..=MAX_POLYGON_VERTICES).contains(&len) {
+            return None;
+        }
+        // 隣り合う辺の外積の符号が全て揃っていれば凸
+        let crosses: Vec<f32> = (0..len)
+            .map(|i| {
+                let (a, b, c) = (vertices[i], vertices[(i + 1) % len], vertices[(i + 2) % len]);
+                (b - a).perp_dot(c - b)
+            })
+            .collect();
+        let counter_clockwise = crosses.iter().all(|cross| *cross > EPSILON);
+        let clockwise = crosses.iter().all(|cross| *cross < -EPSILON);
+        if !counter_clockwise && !clockwise {
+            return None;
+        }
+        let mut points = [Vec2::ZERO; MAX_POLYGON_VERTICES];
+        points[..len].copy_from_slice(vertices);
+        if clockwise {
+            points[..len].reverse();
+        }
+        Some(Self { points, len })
+    }
+
+    /// 頂点を返す関数
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.points[..self.len]
+    }
+}
+
+/// 衝突判定のグループを管理するコンポーネント
+/// `membership`は自分が属するグループ、`filter`は衝突する相手のグループをビットで表す
+/// お互いのグループが相手の`filter`に含まれる場合だけ衝突する。無い場合は全てのグループと衝突する
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollisionLayers {
+    pub membership: u32,
+    pub filter: u32,
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::new(CollisionLayers::ALL, CollisionLayers::ALL)
+    }
+}
+
+impl CollisionLayers {
+    /// 全てのグループ
+    pub const ALL: u32 = u32::MAX;
+    /// どのグループにも属さない
+    pub const NONE: u32 = 0;
+
+    /// 属するグループと衝突する相手のグループを指定して作成する関数
+    pub const fn new(membership: u32, filter: u32) -> Self {
+        Self { membership, filter }
+    }
+
+    /// 相手と衝突するかを返す関数
+    pub fn interacts_with(&self, other: &CollisionLayers) -> bool {
+        self.membership & other.filter != 0 && other.membership & self.filter != 0
+    }
+}
+
+/// 2つの形状の接触情報
+/// 法線は1つ目の形状から2つ目の形状へ向かう
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// 接触点
+    pub point: Vec2,
+    /// 接触面の単位法線
+    pub normal: Vec2,
+    /// めり込みの深さ
+    pub penetration: f32,
+}
+
+impl Collider {
+    /// 半径を指定して円の形状を作成する関数
+    pub fn circle(radius: f32) -> Self {
+        Self::Circle(Circle::new(radius))
+    }
+
+    /// 幅と高さを指定して回転しない矩形の形状を作成する関数
+    pub fn rectangle(width: f32, height: f32) -> Self {
+        Self::Aabb(Rectangle::new(width, height))
+    }
+
+    /// 幅と高さを指定して回転する矩形の形状を作成する関数
+    pub fn oriented_rectangle(width: f32, height: f32) -> Self {
+        Self::Obb(Rectangle::new(width, height))
+    }
+
+    /// 半径と中心部分の長さを指定してカプセルの形状を作成する関数
+    pub fn capsule(radius: f32, length: f32) -> Self {
+        Self::Capsule(Capsule2d::new(radius, length))
+    }
+
+    /// ローカル座標の2点を結ぶ線分の形状を作成する関数
+    pub fn segment(start: Vec2, end: Vec2) -> Self {
+        Self::Segment(Segment2d::new(start, end))
+    }
+
+    /// ローカル座標の頂点から凸多角形の形状を作成する関数
+    /// 頂点の数が合わない場合と、凸でない場合は`None`を返す
+    pub fn polygon(vertices: &[Vec2]) -> Option<Self> {
+        ConvexVertices::new(vertices).map(Self::Polygon)
+    }
+
+    /// 指定した位置と回転での境界ボックスを返す関数
+    pub fn aabb(&self, isometry: Isometry2d) -> Aabb2d {
+        match self {
+            Self::Circle(circle) => circle.aabb_2d(isometry),
+            Self::Aabb(rectangle) => rectangle.aabb_2d(isometry.translation),
+            Self::Obb(rectangle) => rectangle.aabb_2d(isometry),
+            Self::Capsule(capsule) => capsule.aabb_2d(isometry),
+            Self::Segment(segment) => Aabb2d::from_point_cloud(isometry, &segment.vertices),
+            Self::Polygon(polygon) => Aabb2d::from_point_cloud(isometry, polygon.vertices()),
+        }
+    }
+
+    /// 他の形状との接触を判定する関数（狭域判定）
+    /// 接触していなければ`None`を返す
+    pub fn contact(
+        &self,
+        isometry: Isometry2d,
+        other: &Collider,
+        other_isometry: Isometry2d,
+    ) -> Option<Contact> {
+        // 円同士は頻繁に判定するので、直接計算する
+        if let (Self::Circle(a), Self::Circle(b)) = (self, other) {
+            return contact_circles(isometry.translation, a.radius, other_isometry.translation, b.radius);
+        }
+        contact_hulls(&Hull::new(self, isometry), &Hull::new(other, other_isometry))
+    }
+}
+
+/// 位置と回転から形状の配置を作成する関数
+pub fn isometry(position: &Position, rotation: Option<&Rotation>) -> Isometry2d {
+    Isometry2d::new(**position, Rot2::radians(rotation.map_or(0.0, |r| **r)))
+}
+
+/// 凸包の芯と丸みの半径で表した形状
+/// 円は点、カプセルと線分は線分、矩形と多角形は頂点を並べた芯を持つ
+struct Hull {
+    points: [Vec2; MAX_POLYGON_VERTICES],
+    len: usize,
+    radius: f32,
+}
+
+impl Hull {
+    /// 形状と配置から凸包を作成する関数
+    fn new(collider: &Collider, isometry: Isometry2d) -> Self {
+        match collider {
+            Collider::Circle(circle) => Self::from_points(&[isometry.translation], circle.radius),
+            Collider::Aabb(rectangle) => Self::rectangle(rectangle, Isometry2d::from_translation(isometry.translation)),
+            Collider::Obb(rectangle) => Self::rectangle(rectangle, isometry),
+            Collider::Capsule(capsule) => {
+                let top = isometry * Vec2::new(0.0, capsule.half_length);
+                let bottom = isometry * Vec2::new(0.0, -capsule.half_length);
+                Self::from_points(&[bottom, top], capsule.radius)
+            }
+            Collider::Segment(segment) => Self::from_points(&segment.vertices.map(|point| isometry * point), 0.0),
+            Collider::Polygon(polygon) => {
+                let mut points = [Vec2::ZERO; MAX_POLYGON_VERTICES];
+                for (point, vertex) in points.iter_mut().zip(polygon.vertices()) {
+                    *point = isometry * *vertex;
+                }
+                Self::from_points(&points[..polygon.vertices().len()], 0.0)
+            }
+        }
+    }
+
+    /// 芯の点と丸みの半径から凸包を作成する関数
+    fn from_points(points: &[Vec2], radius: f32) -> Self {
+        let mut hull = Self {
+            points: [Vec2::ZERO; MAX_POLYGON_VERTICES],
+            len: points.len(),
+            radius,
+        };
+        hull.points[..points.len()].copy_from_slice(points);
+        hull
+    }
+
+    /// 矩形の4頂点を反時計回りに並べた凸包を作成する関数
+    fn rectangle(rectangle: &Rectangle, isometry: Isometry2d) -> Self {
+        let Vec2 { x, y } = rectangle.half_size;
+        Self::from_points(&[
+            isometry * Vec2::new(-x, -y),
+            isometry * Vec2::new(x, -y),
+            isometry * Vec2::new(x, y),
+            isometry * Vec2::new(-x, y),
+        ], 0.0)
+    }
+
+    fn points(&self) -> &[Vec2] {
+        &self.points[..self.len]
+    }
+
+    /// 芯の辺を返す関数
+    /// 点は長さ0の辺を、線分は1本の辺を持つ
+    fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let count = if self.len == 2 { 1 } else { self.len };
+        (0..count).map(|i| (self.points[i], self.points[(i + 1) % self.len]))
+    }
+
+    fn center(&self) -> Vec2 {
+        self.points().iter().sum::<Vec2>() / self.len as f32
+    }
+
+    /// 軸に射影した芯の範囲を返す関数
+    fn project(&self, axis: Vec2) -> (f32, f32) {
+        self.points().iter().fold((f32::MAX, f32::MIN), |(min, max), point| {
+            let value = point.dot(axis);
+            (min.min(value), max.max(value))
+        })
+    }
+
+    /// 指定した向きに最も遠い芯の点を返す関数
+    /// 辺が向きに垂直な場合は、その辺の中点を返す
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let (_, max) = self.project(direction);
+        let (sum, count) = self.points().iter()
+            .filter(|point| point.dot(direction) >= max - SUPPORT_TOLERANCE)
+            .fold((Vec2::ZERO, 0), |(sum, count), point| (sum + *point, count + 1));
+        sum / count as f32
+    }
+
+    /// 分離軸の候補を返す関数
+    fn axes(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.edges().flat_map(|(start, end)| {
+            let direction = (end - start).normalize_or_zero();
+            // 線分は端を越えた位置でも分離できるように、向きも候補にする
+            let along = (self.len == 2).then_some(direction);
+            [Some(direction.perp()), along].into_iter().flatten()
+        })
+    }
+}
+
+/// 円同士の接触を判定する関数
+fn contact_circles(center_a: Vec2, radius_a: f32, center_b: Vec2, radius_b: f32) -> Option<Contact> {
+    let delta = center_b - center_a;
+    let radii = radius_a + radius_b;
+    let distance_squared = delta.length_squared();
+    if distance_squared > radii * radii {
+        return None;
+    }
+    let distance = distance_squared.sqrt();
+    let normal = if distance > EPSILON { delta / distance } else { Vec2::X };
+    Some(Contact {
+        point: center_a + normal * (radius_a - (radii - distance) / 2.0),
+        normal,
+        penetration: radii - distance,
+    })
+}
+
+/// 凸包同士の接触を判定する関数
+/// 芯が離れていれば最近点の距離から、重なっていれば分離軸から接触を求める
+fn contact_hulls(a: &Hull, b: &Hull) -> Option<Contact> {
+    let radii = a.radius + b.radius;
+    match min_overlap(a, b) {
+        Some((normal, overlap)) => {
+            let point_a = a.support(normal) + normal * a.radius;
+            let point_b = b.support(-normal) - normal * b.radius;
+            Some(Contact {
+                point: (point_a + point_b) / 2.0,
+                normal,
+                penetration: overlap + radii,
+            })
+        }
+        None => {
+            let (point_a, point_b) = closest_points(a, b);
+            let delta = point_b - point_a;
+            let distance = delta.length();
+            if distance >= radii || distance <= EPSILON {
+                return None;
+            }
+            let normal = delta / distance;
+            Some(Contact {
+                point: (point_a + normal * a.radius + point_b - normal * b.radius) / 2.0,
+                normal,
+                penetration: radii - distance,
+            })
+        }
+    }
+}
+
+/// 分離軸定理で芯の重なりを判定する関数
+/// 重なっていれば、最も重なりの小さい軸（aからbへの向き）と重なりの量を返す
+fn min_overlap(a: &Hull, b: &Hull) -> Option<(Vec2, f32)> {
+    let between = (b.center() - a.center()).normalize_or_zero();
+    let mut best: Option<(Vec2, f32)> = None;
+    for axis in a.axes().chain(b.axes()).chain([between]) {
+        if axis == Vec2::ZERO {
+            continue;
+        }
+        let (min_a, max_a) = a.project(axis);
+        let (min_b, max_b) = b.project(axis);
+        let overlap = (max_a - min_b).min(max_b - min_a);
+        if overlap < 0.0 {
+            return None;
+        }
+        if best.is_none_or(|(_, best_overlap)| overlap < best_overlap) {
+            best = Some((axis, overlap));
+        }
+    }
+    let (axis, overlap) = best.unwrap_or((Vec2::X, 0.0));
+    // 法線がaからbへ向くように揃える
+    let axis = if axis.dot(b.center() - a.center()) < 0.0 { -axis } else { axis };
+    Some((axis, overlap))
+}
+
+/// 離れている芯同士の最近点を返す関数
+fn closest_points(a: &Hull, b: &Hull) -> (Vec2, Vec2) {
+    let from_a = a.points().iter().flat_map(|point| {
+        b.edges().map(|(start, end)| (*point, closest_on_segment(*point, start, end)))
+    });
+    let from_b = b.points().iter().flat_map(|point| {
+        a.edges().map(|(start, end)| (closest_on_segment(*point, start, end), *point))
+    });
+    from_a.chain(from_b)
+        .min_by(|(a1, b1), (a2, b2)| a1.distance_squared(*b1).total_cmp(&a2.distance_squared(*b2)))
+        .unwrap_or((a.center(), b.center()))
+}
+
+/// 線分上で点に最も近い点を返す関数
+fn closest_on_segment(point: Vec2, start: Vec2, end: Vec2) -> Vec2 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared <= EPSILON * EPSILON {
+        return start;
+    }
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    start + segment * t
+}
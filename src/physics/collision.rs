@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+
+use super::{
+    BroadPhase,
+    Collider,
+    CollisionLayers,
+    Contact,
+    DeterministicMode,
+    PhysicsSet,
+    Position,
+    Rotation,
+    Sensor,
+    StableId,
+    Velocity,
+    collider,
+};
+
+/// めり込みを許す深さ
+/// 接触し続ける物体が押し出しで震えないように、少しだけ残す
+const PENETRATION_SLOP: f32 = 0.01;
+/// 1ステップで押し出すめり込みの割合
+const CORRECTION_PERCENT: f32 = 0.8;
+
+/// 質量を管理するコンポーネント
+/// 無い場合は1として扱い、0以下の場合は動かない物体として扱う
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deref, DerefMut)]
+pub struct Mass(pub f32);
+
+impl Default for Mass {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl Mass {
+    /// 質量の逆数を返す関数
+    pub fn inverse(&self) -> f32 {
+        if **self > 0.0 { 1.0 / **self } else { 0.0 }
+    }
+}
+
+/// 反発係数を管理するコンポーネント
+/// 1で完全弾性衝突、0で完全非弾性衝突になる。無い場合は1として扱う
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deref, DerefMut)]
+pub struct Restitution(pub f32);
+
+impl Default for Restitution {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// 接触している2つのエンティティ
+/// 法線は`entity1`から`entity2`へ向かう
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactPair {
+    pub entity1: Entity,
+    pub entity2: Entity,
+    pub contact: Contact,
+}
+
+/// 今回の固定タイムステップで見つかった接触を管理するリソース
+#[derive(Resource, Debug, Default, Deref, DerefMut)]
+pub struct Contacts(pub Vec<ContactPair>);
+
+/// 形状同士の接触を見つけ、力積で速度を変えるプラグイン
+/// `Velocity`の無い形状は、動かない物体として扱い、動かない形状同士は判定しない
+/// `Sensor`の形状は接触を見つけるだけで、速度を変えない
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<BroadPhase>()
+            .init_resource::<Contacts>()
+            .add_systems(FixedUpdate, (
+                update_broad_phase,
+                detect_contacts,
+                resolve_contacts,
+            ).chain().in_set(PhysicsSet::Collide))
+        ;
+    }
+}
+
+/// 衝突判定に使うコンポーネント
+type ColliderData = (
+    Entity,
+    &'static Collider,
+    &'static Position,
+    Option<&'static Rotation>,
+    Option<&'static CollisionLayers>,
+    Has<Velocity>,
+);
+
+/// 全ての形状の境界ボックスを空間ハッシュに登録し直す関数（広域判定）
+fn update_broad_phase(
+    query: Query<(ColliderData, &StableId)>,
+    deterministic: Res<DeterministicMode>,
+    mut broad_phase: ResMut<BroadPhase>,
+) {
+    info_once!("update_broad_phase");
+
+    let mut colliders: Vec<_> = query.iter().collect();
+    // 決定論的モードでは、クエリの順番に左右されないように安定したIDの順に登録する
+    // 組と接触は登録した順に並ぶので、接触を解決する順番も揃う
+    if **deterministic {
+        colliders.sort_unstable_by_key(|(_, id)| **id);
+    }
+
+    broad_phase.clear();
+    for ((entity, collider, position, rotation, layers, _), _) in colliders {
+        let aabb = collider.aabb(collider::isometry(position, rotation));
+        broad_phase.insert(entity, aabb, layers.copied().unwrap_or_default());
+    }
+    broad_phase.update_pairs();
+}
+
+/// 広域判定で絞り込んだ組から接触を見つける関数（狭域判定）
+pub(super) fn detect_contacts(
+    query: Query<ColliderData>,
+    broad_phase: Res<BroadPhase>,
+    mut contacts: ResMut<Contacts>,
+) {
+    info_once!("detect_contacts");
+
+    contacts.clear();
+    for &(entity1, entity2) in broad_phase.pairs() {
+        let Ok([a, b]) = query.get_many([entity1, entity2]) else {
+            continue;
+        };
+        let (entity1, collider1, position1, rotation1, layers1, moving1) = a;
+        let (entity2, collider2, position2, rotation2, layers2, moving2) = b;
+        // 壁同士のように、どちらも動かない組は接触しない
+        if !moving1 && !moving2 {
+            continue;
+        }
+        // 広域判定の後にグループが変わっていても衝突しないように、もう一度確かめる
+        if !layers1.copied().unwrap_or_default().interacts_with(&layers2.copied().unwrap_or_default()) {
+            continue;
+        }
+        if let Some(contact) = collider1.contact(
+            collider::isometry(position1, rotation1),
+            collider2,
+            collider::isometry(position2, rotation2),
+        ) {
+            contacts.push(ContactPair { entity1, entity2, contact });
+        }
+    }
+}
+
+/// 接触の解決に使うコンポーネント
+type BodyData = (
+    &'static mut Position,
+    Option<&'static mut Velocity>,
+    Option<&'static Mass>,
+    Option<&'static Restitution>,
+    Has<Sensor>,
+);
+
+/// 接触法線に沿った力積で速度を変え、めり込みを押し出す関数
+pub(super) fn resolve_contacts(
+    mut query: Query<BodyData>,
+    contacts: Res<Contacts>,
+) {
+    info_once!("resolve_contacts");
+
+    for pair in contacts.iter() {
+        let Ok([a, b]) = query.get_many_mut([pair.entity1, pair.entity2]) else {
+            continue;
+        };
+        let (mut position1, mut velocity1, mass1, restitution1, sensor1) = a;
+        let (mut position2, mut velocity2, mass2, restitution2, sensor2) = b;
+        if sensor1 || sensor2 {
+            continue;
+        }
+
+        // 速度の無い物体は動かない
+        let inverse1 = velocity1.as_ref().map_or(0.0, |_| mass1.copied().unwrap_or_default().inverse());
+        let inverse2 = velocity2.as_ref().map_or(0.0, |_| mass2.copied().unwrap_or_default().inverse());
+        let inverse_sum = inverse1 + inverse2;
+        if inverse_sum <= 0.0 {
+            continue;
+        }
+        let normal = pair.contact.normal;
+
+        // 近づいている場合だけ、法線方向の相対速度を反発係数に応じて反転させる
+        let v1 = velocity1.as_deref().map_or(Vec2::ZERO, |v| **v);
+        let v2 = velocity2.as_deref().map_or(Vec2::ZERO, |v| **v);
+        let approach = (v2 - v1).dot(normal);
+        if approach < 0.0 {
+            // 反発係数は小さい方を使う
+            let restitution = restitution1.copied().unwrap_or_default().0
+                .min(restitution2.copied().unwrap_or_default().0);
+            let impulse = normal * (-(1.0 + restitution) * approach / inverse_sum);
+            if let Some(velocity) = velocity1.as_deref_mut() {
+                **velocity -= impulse * inverse1;
+            }
+            if let Some(velocity) = velocity2.as_deref_mut() {
+                **velocity += impulse * inverse2;
+            }
+        }
+
+        // めり込みを質量の逆数の比で押し出す
+        let depth = (pair.contact.penetration - PENETRATION_SLOP).max(0.0);
+        let correction = normal * (depth * CORRECTION_PERCENT / inverse_sum);
+        **position1 -= correction * inverse1;
+        **position2 += correction * inverse2;
+    }
+}
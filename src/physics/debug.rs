@@ -0,0 +1,171 @@
+use bevy::{
+    prelude::*,
+    color::palettes::css,
+    ecs::entity::EntityHashSet,
+    gizmos::config::GizmoConfigStore,
+    math::bounding::BoundingVolume,
+};
+
+use super::{BroadPhase, Collider, CollisionLayers, Contacts, Position, Rotation, Velocity, collider};
+
+/// デバッグ表示を切り替えるキー
+pub const KEY_TOGGLE_PHYSICS_DEBUG: KeyCode = KeyCode::F3;
+/// 接触点の円の半径
+const CONTACT_RADIUS: f32 = 2.0;
+/// 接触法線の矢印の長さ
+const NORMAL_LENGTH: f32 = 16.0;
+/// 速度の矢印の長さの倍率（1秒間に進む距離に掛ける）
+const VELOCITY_SCALE: f32 = 0.25;
+/// 接触していない形状の色の透明度
+const IDLE_ALPHA: f32 = 0.4;
+/// 全てのグループに属する形状の色
+const DEFAULT_LAYER_COLOR: Srgba = css::WHITE;
+const AABB_COLOR: Srgba = css::DARK_GRAY;
+const CONTACT_COLOR: Srgba = css::RED;
+const NORMAL_COLOR: Srgba = css::YELLOW;
+const VELOCITY_COLOR: Srgba = css::LIME;
+
+/// 物理演算のデバッグ表示の設定を管理するリソース
+/// `enabled`で全体を、残りの項目で描くものを切り替える
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct PhysicsDebug {
+    pub enabled: bool,
+    /// 形状の輪郭
+    pub colliders: bool,
+    /// 広域判定の境界ボックス
+    pub aabbs: bool,
+    /// 接触点と法線
+    pub contacts: bool,
+    /// 速度の矢印
+    pub velocities: bool,
+}
+
+impl Default for PhysicsDebug {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            colliders: true,
+            aabbs: true,
+            contacts: true,
+            velocities: true,
+        }
+    }
+}
+
+/// キーで切り替えて、形状や接触をギズモで描くプラグイン
+/// 形状は衝突判定のグループごとの色で、接触している間は濃く描く
+/// ギズモの無いヘッドレスモードでは描かない
+pub struct PhysicsDebugPlugin;
+
+impl Plugin for PhysicsDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PhysicsDebug>()
+            .add_systems(Update, (
+                toggle_physics_debug,
+                draw_physics_debug
+                    .run_if(resource_exists::<GizmoConfigStore>)
+                    .run_if(|settings: Res<PhysicsDebug>| settings.enabled),
+            ).chain())
+        ;
+    }
+}
+
+/// キーが押されたらデバッグ表示を切り替える関数
+fn toggle_physics_debug(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<PhysicsDebug>,
+) {
+    info_once!("toggle_physics_debug");
+
+    if keyboard_input.just_pressed(KEY_TOGGLE_PHYSICS_DEBUG) {
+        settings.enabled = !settings.enabled;
+        debug!("physics debug: {}", settings.enabled);
+    }
+}
+
+/// デバッグ表示に使うコンポーネント
+type DebugData = (
+    Entity,
+    &'static Collider,
+    &'static Position,
+    Option<&'static Rotation>,
+    Option<&'static CollisionLayers>,
+    Option<&'static Velocity>,
+);
+
+/// 形状、境界ボックス、接触、速度をギズモで描く関数
+/// 描画用の`Transform`ではなく、物理演算上の位置と回転で描く
+fn draw_physics_debug(
+    mut gizmos: Gizmos,
+    settings: Res<PhysicsDebug>,
+    query: Query<DebugData>,
+    broad_phase: Res<BroadPhase>,
+    contacts: Res<Contacts>,
+) {
+    info_once!("draw_physics_debug");
+
+    if settings.aabbs {
+        for (_, aabb) in broad_phase.entries() {
+            gizmos.rect_2d(aabb.center(), aabb.half_size() * 2.0, AABB_COLOR);
+        }
+    }
+
+    let touching: EntityHashSet = contacts.iter()
+        .flat_map(|pair| [pair.entity1, pair.entity2])
+        .collect();
+    for (entity, collider, position, rotation, layers, velocity) in &query {
+        if settings.colliders {
+            let color = layer_color(&layers.copied().unwrap_or_default());
+            let color = if touching.contains(&entity) { color } else { color.with_alpha(IDLE_ALPHA) };
+            draw_collider(&mut gizmos, collider, collider::isometry(position, rotation), color);
+        }
+        if let Some(velocity) = velocity.filter(|_| settings.velocities) {
+            gizmos.arrow_2d(**position, **position + **velocity * VELOCITY_SCALE, VELOCITY_COLOR);
+        }
+    }
+
+    if settings.contacts {
+        for pair in contacts.iter() {
+            let point = pair.contact.point;
+            gizmos.circle_2d(point, CONTACT_RADIUS, CONTACT_COLOR);
+            gizmos.arrow_2d(point, point + pair.contact.normal * NORMAL_LENGTH, NORMAL_COLOR);
+        }
+    }
+}
+
+/// 形状の輪郭を描く関数
+fn draw_collider(gizmos: &mut Gizmos, collider: &Collider, isometry: Isometry2d, color: Srgba) {
+    match collider {
+        Collider::Circle(circle) => {
+            gizmos.primitive_2d(circle, isometry, color);
+        }
+        Collider::Aabb(rectangle) => {
+            gizmos.primitive_2d(rectangle, Isometry2d::from_translation(isometry.translation), color);
+        }
+        Collider::Obb(rectangle) => {
+            gizmos.primitive_2d(rectangle, isometry, color);
+        }
+        Collider::Capsule(capsule) => {
+            gizmos.primitive_2d(capsule, isometry, color);
+        }
+        Collider::Segment(segment) => {
+            gizmos.primitive_2d(segment, isometry, color);
+        }
+        Collider::Polygon(polygon) => {
+            let vertices = polygon.vertices();
+            let closed = vertices.iter().chain(vertices.first()).map(|vertex| isometry * *vertex);
+            gizmos.linestrip_2d(closed, color);
+        }
+    }
+}
+
+/// 形状が属するグループから色を返す関数
+/// 最も小さいグループの番号ごとに、色相を黄金角ずつずらす
+pub fn layer_color(layers: &CollisionLayers) -> Srgba {
+    if layers.membership == CollisionLayers::ALL || layers.membership == CollisionLayers::NONE {
+        return DEFAULT_LAYER_COLOR;
+    }
+    let hue = (layers.membership.trailing_zeros() as f32 * 137.5) % 360.0;
+    Hsla::hsl(hue, 0.8, 0.6).into()
+}
@@ -0,0 +1,103 @@
+use bevy::{
+    prelude::*,
+    ecs::{component::HookContext, world::DeferredWorld},
+};
+
+use super::{AngularVelocity, Position, Rotation, Velocity};
+
+/// FNV-1aの初期値
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1aの素数
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 物理演算の順番を決める安定したIDを管理するコンポーネント
+/// `Entity`と違って使い回されず、生成した順に1から振られる
+/// 0のまま追加された場合だけ振るので、リプレイでは保存したIDをそのまま使える
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deref, DerefMut)]
+#[component(on_add = assign_stable_id)]
+pub struct StableId(pub u64);
+
+/// 次に振る安定したIDを管理するリソース
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Deref, DerefMut)]
+pub struct NextStableId(pub u64);
+
+impl Default for NextStableId {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// 決定論的モードを管理するリソース
+/// 有効にすると、広域判定と連続的な衝突判定で安定したIDの順に形状を扱い、
+/// 接触の判定と解決の順番がクエリの順番に左右されなくなる
+/// 同じ入力からは、実行するたびにビット単位で同じ結果になる
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Deref, DerefMut)]
+pub struct DeterministicMode(pub bool);
+
+/// 安定したIDを振り、決定論的モードを切り替えられるようにするプラグイン
+pub struct DeterminismPlugin;
+
+impl Plugin for DeterminismPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<NextStableId>()
+            .init_resource::<DeterministicMode>()
+        ;
+    }
+}
+
+/// まだIDが無ければ、次の安定したIDを振る関数
+fn assign_stable_id(mut world: DeferredWorld, context: HookContext) {
+    if world.get::<StableId>(context.entity).is_none_or(|id| **id != 0) {
+        return;
+    }
+    let Some(mut next) = world.get_resource_mut::<NextStableId>() else {
+        return;
+    };
+    let id = **next;
+    **next += 1;
+    if let Some(mut stable_id) = world.get_mut::<StableId>(context.entity) {
+        **stable_id = id;
+    }
+}
+
+/// 状態のハッシュに使うコンポーネント
+type StateData = (
+    &'static StableId,
+    &'static Position,
+    Option<&'static Rotation>,
+    Option<&'static Velocity>,
+    Option<&'static AngularVelocity>,
+);
+
+/// 全ての物体の位置、回転、速度から状態のハッシュを返す関数
+/// 安定したIDの順に、浮動小数点数のビットをリトルエンディアンでFNV-1aに通す
+/// アルゴリズムが決まっているので、ツールチェーンやプラットフォームが違っても同じ値になる
+/// リプレイや同期のずれを見つけるために使う
+pub fn state_hash(world: &mut World) -> u64 {
+    let mut query = world.query::<StateData>();
+    let mut bodies: Vec<_> = query.iter(world).collect();
+    bodies.sort_unstable_by_key(|(id, ..)| **id);
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for (id, position, rotation, velocity, angular_velocity) in bodies {
+        hash = fnv1a(hash, &id.to_le_bytes());
+        let velocity = velocity.map_or(Vec2::ZERO, |velocity| **velocity);
+        for value in [
+            position.x,
+            position.y,
+            rotation.map_or(0.0, |rotation| **rotation),
+            velocity.x,
+            velocity.y,
+            angular_velocity.map_or(0.0, |angular_velocity| **angular_velocity),
+        ] {
+            hash = fnv1a(hash, &value.to_bits().to_le_bytes());
+        }
+    }
+    hash
+}
+
+/// FNV-1aでバイト列をハッシュに混ぜる関数
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
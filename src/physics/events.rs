@@ -0,0 +1,165 @@
+use bevy::{
+    prelude::*,
+    math::bounding::{Aabb2d, BoundingVolume},
+    platform::collections::HashMap,
+};
+
+use super::{ContactPair, Contacts, PhysicsSet, collision};
+
+/// 2つの形状が触れ始めたときのイベント
+/// バッファ付きのイベントとして送られ、両方のエンティティのオブザーバーにも通知される
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct CollisionStarted {
+    pub entity1: Entity,
+    pub entity2: Entity,
+    /// 接触点
+    pub point: Vec2,
+    /// `entity1`から`entity2`へ向かう接触面の法線
+    pub normal: Vec2,
+}
+
+/// 2つの形状が離れたときのイベント
+/// 接触点と法線は、最後に触れていたときの値になる
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct CollisionEnded {
+    pub entity1: Entity,
+    pub entity2: Entity,
+    pub point: Vec2,
+    pub normal: Vec2,
+}
+
+/// 形状が画面端の壁に当たったときのイベント
+/// バッファ付きのイベントとして送られ、当たったエンティティのオブザーバーにも通知される
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct WallHit {
+    pub entity: Entity,
+    pub side: WallSide,
+    /// 壁の上の接触点
+    pub point: Vec2,
+    /// 画面の内側へ向かう壁の法線
+    pub normal: Vec2,
+}
+
+/// 画面端の向き
+/// 画面端の壁のエンティティには、コンポーネントとして追加される
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WallSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl WallSide {
+    pub const ALL: [WallSide; 4] = [WallSide::Left, WallSide::Right, WallSide::Top, WallSide::Bottom];
+
+    /// 画面の内側へ向かう壁の法線を返す関数
+    pub fn normal(&self) -> Vec2 {
+        match self {
+            Self::Left => Vec2::X,
+            Self::Right => Vec2::NEG_X,
+            Self::Top => Vec2::NEG_Y,
+            Self::Bottom => Vec2::Y,
+        }
+    }
+
+    /// 境界ボックスがこの壁からはみ出しているかを返す関数
+    pub fn is_outside(&self, aabb: &Aabb2d, bounds: &Aabb2d) -> bool {
+        match self {
+            Self::Left => aabb.min.x < bounds.min.x,
+            Self::Right => aabb.max.x > bounds.max.x,
+            Self::Top => aabb.max.y > bounds.max.y,
+            Self::Bottom => aabb.min.y < bounds.min.y,
+        }
+    }
+
+    /// 境界ボックスの中心をこの壁の上に移した点を返す関数
+    pub fn point(&self, aabb: &Aabb2d, bounds: &Aabb2d) -> Vec2 {
+        let center = aabb.center();
+        match self {
+            Self::Left => center.with_x(bounds.min.x),
+            Self::Right => center.with_x(bounds.max.x),
+            Self::Top => center.with_y(bounds.max.y),
+            Self::Bottom => center.with_y(bounds.min.y),
+        }
+    }
+}
+
+impl CollisionStarted {
+    /// 指定したエンティティの相手を返す関数
+    pub fn other(&self, entity: Entity) -> Entity {
+        if entity == self.entity1 { self.entity2 } else { self.entity1 }
+    }
+}
+
+impl CollisionEnded {
+    /// 指定したエンティティの相手を返す関数
+    pub fn other(&self, entity: Entity) -> Entity {
+        if entity == self.entity1 { self.entity2 } else { self.entity1 }
+    }
+}
+
+/// 前回の固定タイムステップで触れていた組を管理するリソース
+/// キーはエンティティの小さい順に並べる
+#[derive(Resource, Debug, Default, Deref, DerefMut)]
+pub(super) struct ActiveContacts(HashMap<(Entity, Entity), ContactPair>);
+
+/// 接触の始まりと終わりをイベントで知らせるプラグイン
+pub struct CollisionEventsPlugin;
+
+impl Plugin for CollisionEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<CollisionStarted>()
+            .add_event::<CollisionEnded>()
+            .add_event::<WallHit>()
+            .init_resource::<ActiveContacts>()
+            .add_systems(FixedUpdate, send_collision_events
+                .after(collision::resolve_contacts)
+                .in_set(PhysicsSet::Collide))
+        ;
+    }
+}
+
+/// 前回と今回の接触を比べて、始まりと終わりのイベントを送る関数
+pub(super) fn send_collision_events(
+    mut commands: Commands,
+    contacts: Res<Contacts>,
+    mut active: ResMut<ActiveContacts>,
+    mut started_events: EventWriter<CollisionStarted>,
+    mut ended_events: EventWriter<CollisionEnded>,
+) {
+    info_once!("send_collision_events");
+
+    let mut previous = std::mem::take(&mut **active);
+    for pair in contacts.iter() {
+        let key = (pair.entity1.min(pair.entity2), pair.entity1.max(pair.entity2));
+        active.insert(key, *pair);
+        if previous.remove(&key).is_some() {
+            continue;
+        }
+        let event = CollisionStarted {
+            entity1: pair.entity1,
+            entity2: pair.entity2,
+            point: pair.contact.point,
+            normal: pair.contact.normal,
+        };
+        started_events.write(event);
+        commands.trigger_targets(event, [pair.entity1, pair.entity2]);
+    }
+
+    // 今回触れていない組は離れた
+    // ハッシュの順番に左右されないように、エンティティの順に並べる
+    let mut ended: Vec<_> = previous.into_iter().collect();
+    ended.sort_unstable_by_key(|(key, _)| *key);
+    for (_, pair) in ended {
+        let event = CollisionEnded {
+            entity1: pair.entity1,
+            entity2: pair.entity2,
+            point: pair.contact.point,
+            normal: pair.contact.normal,
+        };
+        ended_events.write(event);
+        commands.trigger_targets(event, [pair.entity1, pair.entity2]);
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::{
+    prelude::*,
+    ecs::{component::HookContext, world::DeferredWorld},
+};
+
+use super::{PhysicsSet, StableId};
+
+/// 物理演算上の位置を管理するコンポーネント
+/// 追加されたときに`Transform`の位置で初期化される
+/// 描画用の`Transform`は、前回と今回の位置を補間して決まる
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+#[component(on_add = init_position)]
+#[require(Transform, PreviousPosition, StableId)]
+pub struct Position(pub Vec2);
+
+/// 1つ前の固定タイムステップでの位置を管理するコンポーネント
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+pub struct PreviousPosition(pub Vec2);
+
+/// 物理演算上の回転（ラジアン）を管理するコンポーネント
+/// 追加されたときに`Transform`のZ軸回りの回転で初期化される
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+#[component(on_add = init_rotation)]
+#[require(Transform, PreviousRotation)]
+pub struct Rotation(pub f32);
+
+/// 1つ前の固定タイムステップでの回転を管理するコンポーネント
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+pub struct PreviousRotation(pub f32);
+
+/// 速度を管理するコンポーネント
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+#[require(Position)]
+pub struct Velocity(pub Vec2);
+
+/// 加速度を管理するコンポーネント
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+#[require(Velocity)]
+pub struct Acceleration(pub Vec2);
+
+/// 速度の減衰率（1秒あたり）を管理するコンポーネント
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+#[require(Velocity)]
+pub struct LinearDamping(pub f32);
+
+/// 速さの上限を管理するコンポーネント
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deref, DerefMut)]
+#[require(Velocity)]
+pub struct MaxSpeed(pub f32);
+
+/// 重力加速度
+/// リソースとして全体の重力を、コンポーネントとしてエンティティごとの重力を設定する
+/// コンポーネントがある場合は、リソースの値より優先される
+#[derive(Resource, Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+pub struct Gravity(pub Vec2);
+
+/// 角速度（ラジアン毎秒）を管理するコンポーネント
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Deref, DerefMut)]
+#[require(Rotation)]
+pub struct AngularVelocity(pub f32);
+
+/// 速度による移動を固定タイムステップで行い、描画位置を補間するプラグイン
+pub struct KinematicsPlugin;
+
+impl Plugin for KinematicsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Gravity>()
+            .add_systems(FixedUpdate, (
+                save_previous_position,
+                apply_velocity,
+            ).chain().in_set(PhysicsSet::Integrate))
+            .add_systems(
+                RunFixedMainLoop,
+                interpolate_transform.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+            )
+        ;
+    }
+}
+
+/// 位置を`Transform`の値で初期化する関数
+fn init_position(mut world: DeferredWorld, context: HookContext) {
+    let Some(translation) = world
+        .get::<Transform>(context.entity)
+        .map(|transform| transform.translation.truncate())
+    else {
+        return;
+    };
+    let mut entity = world.entity_mut(context.entity);
+    if let Some(mut position) = entity.get_mut::<Position>() {
+        **position = translation;
+    }
+    if let Some(mut previous) = entity.get_mut::<PreviousPosition>() {
+        **previous = translation;
+    }
+}
+
+/// 回転を`Transform`の値で初期化する関数
+fn init_rotation(mut world: DeferredWorld, context: HookContext) {
+    let Some(angle) = world
+        .get::<Transform>(context.entity)
+        .map(|transform| transform.rotation.to_euler(EulerRot::ZYX).0)
+    else {
+        return;
+    };
+    let mut entity = world.entity_mut(context.entity);
+    if let Some(mut rotation) = entity.get_mut::<Rotation>() {
+        **rotation = angle;
+    }
+    if let Some(mut previous) = entity.get_mut::<PreviousRotation>() {
+        **previous = angle;
+    }
+}
+
+/// 移動前の位置と回転を保存する関数
+fn save_previous_position(
+    mut position_query: Query<(&Position, &mut PreviousPosition)>,
+    mut rotation_query: Query<(&Rotation, &mut PreviousRotation)>,
+) {
+    info_once!("save_previous_position");
+
+    for (position, mut previous) in &mut position_query {
+        **previous = **position;
+    }
+    for (rotation, mut previous) in &mut rotation_query {
+        **previous = **rotation;
+    }
+}
+
+/// 速度の計算に使うコンポーネント
+type MotionData = (
+    &'static mut Position,
+    &'static mut Velocity,
+    Option<&'static Acceleration>,
+    Option<&'static Gravity>,
+    Option<&'static LinearDamping>,
+    Option<&'static MaxSpeed>,
+);
+
+/// 描画位置の補間に使うコンポーネント
+type InterpolationData = (
+    &'static mut Transform,
+    Option<(&'static Position, &'static PreviousPosition)>,
+    Option<(&'static Rotation, &'static PreviousRotation)>,
+);
+
+/// 描画位置の補間の対象を絞り込むフィルター
+type InterpolationFilter = Or<(With<Position>, With<Rotation>)>;
+
+/// 速度を追加する関数
+/// 加速度と重力で速度を変え、減衰と上限を適用してから位置を進める
+/// 角速度があれば回転も進める
+pub(super) fn apply_velocity(
+    mut query: Query<MotionData>,
+    mut rotation_query: Query<(&mut Rotation, &AngularVelocity)>,
+    gravity: Res<Gravity>,
+    time: Res<Time<Fixed>>,
+) {
+    info_once!("apply_velocity");
+
+    // 実行のたびに同じ結果になるように、固定タイムステップの長さだけを使う
+    let delta = time.timestep().as_secs_f32();
+    for (mut position, mut velocity, acceleration, local_gravity, damping, max_speed) in &mut query {
+        let acceleration = acceleration.map_or(Vec2::ZERO, |a| **a);
+        let gravity = local_gravity.map_or(**gravity, |g| **g);
+        **velocity += (acceleration + gravity) * delta;
+        if let Some(damping) = damping {
+            **velocity *= 1.0 / (1.0 + **damping * delta);
+        }
+        if let Some(max_speed) = max_speed {
+            **velocity = velocity.clamp_length_max(**max_speed);
+        }
+        **position += **velocity * delta;
+    }
+
+    for (mut rotation, angular_velocity) in &mut rotation_query {
+        **rotation += **angular_velocity * delta;
+    }
+}
+
+/// 前回と今回の位置と回転を、固定タイムステップの進み具合で補間して描画に使う関数
+fn interpolate_transform(
+    mut query: Query<InterpolationData, InterpolationFilter>,
+    time: Res<Time<Fixed>>,
+) {
+    info_once!("interpolate_transform");
+
+    let alpha = time.overstep_fraction();
+    for (mut transform, position, rotation) in &mut query {
+        if let Some((position, previous)) = position {
+            let translation = previous.lerp(**position, alpha);
+            transform.translation.x = translation.x;
+            transform.translation.y = translation.y;
+        }
+        if let Some((rotation, previous)) = rotation {
+            let angle = previous.lerp(**rotation, alpha);
+            transform.rotation = Quat::from_rotation_z(angle);
+        }
+    }
+}
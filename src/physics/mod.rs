@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+pub mod broad_phase;
+pub mod ccd;
+pub mod collider;
+pub mod collision;
+pub mod debug;
+pub mod determinism;
+pub mod events;
+pub mod kinematics;
+pub mod sensor;
+pub mod spatial_query;
+pub mod wall;
+
+pub use broad_phase::BroadPhase;
+pub use ccd::{Ccd, CcdPlugin};
+pub use collider::{Collider, CollisionLayers, Contact, ConvexVertices};
+pub use collision::{CollisionPlugin, ContactPair, Contacts, Mass, Restitution};
+pub use debug::{PhysicsDebug, PhysicsDebugPlugin};
+pub use determinism::{DeterminismPlugin, DeterministicMode, NextStableId, StableId};
+pub use events::{CollisionEnded, CollisionEventsPlugin, CollisionStarted, WallHit, WallSide};
+pub use kinematics::{
+    Acceleration,
+    AngularVelocity,
+    Gravity,
+    KinematicsPlugin,
+    LinearDamping,
+    MaxSpeed,
+    Position,
+    PreviousPosition,
+    PreviousRotation,
+    Rotation,
+    Velocity,
+};
+pub use sensor::{CollidingEntities, Sensor, SensorEntered, SensorExited, SensorPlugin, SensorStayed};
+pub use spatial_query::{RayHit, ShapeHit, SpatialQuery, SpatialQueryFilter};
+pub use wall::{PlayArea, WallPlugin, WindowBounds};
+
+/// 物理演算のシステムの実行順を決めるセット
+/// 全て`FixedUpdate`で実行される
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PhysicsSet {
+    /// 速度から位置を更新する
+    Integrate,
+    /// 衝突を判定して速度を変える
+    Collide,
+}
+
+/// 固定タイムステップで物理演算を行うプラグイン
+/// 複数のサンプルから追加されても1回だけ登録される
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .configure_sets(FixedUpdate, (
+                PhysicsSet::Integrate,
+                PhysicsSet::Collide,
+            ).chain())
+            .add_plugins((
+                DeterminismPlugin,
+                KinematicsPlugin,
+                CcdPlugin,
+                CollisionPlugin,
+                CollisionEventsPlugin,
+                SensorPlugin,
+                WallPlugin,
+                PhysicsDebugPlugin,
+            ))
+        ;
+    }
+}
+
+/// まだ追加されていなければ物理演算のプラグインを追加する関数
+pub fn add_physics(app: &mut App) {
+    if !app.is_plugin_added::<PhysicsPlugin>() {
+        app.add_plugins(PhysicsPlugin);
+    }
+}
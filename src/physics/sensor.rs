@@ -0,0 +1,105 @@
+use bevy::{
+    prelude::*,
+    ecs::entity::{EntityHashMap, EntityHashSet},
+};
+
+use super::{Contacts, PhysicsSet, collision};
+
+/// 速度に影響せず、重なっているエンティティだけを調べる形状の目印
+/// ゴールの範囲やアイテムを拾う範囲などに使う
+#[derive(Component, Debug, Default, Clone, Copy)]
+#[require(CollidingEntities)]
+pub struct Sensor;
+
+/// 今重なっているエンティティを管理するコンポーネント
+/// `Sensor`以外の形状にも追加できる
+#[derive(Component, Debug, Default, Clone, PartialEq, Deref, DerefMut)]
+pub struct CollidingEntities(pub EntityHashSet);
+
+/// センサーにエンティティが入ったときのイベント
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SensorEntered {
+    pub sensor: Entity,
+    pub entity: Entity,
+}
+
+/// センサーにエンティティが入り続けているときに、固定タイムステップごとに送られるイベント
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SensorStayed {
+    pub sensor: Entity,
+    pub entity: Entity,
+}
+
+/// センサーからエンティティが出たときのイベント
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SensorExited {
+    pub sensor: Entity,
+    pub entity: Entity,
+}
+
+/// 重なっているエンティティを記録し、センサーの出入りをイベントで知らせるプラグイン
+/// イベントはセンサーのエンティティのオブザーバーにも通知される
+pub struct SensorPlugin;
+
+impl Plugin for SensorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<SensorEntered>()
+            .add_event::<SensorStayed>()
+            .add_event::<SensorExited>()
+            .add_systems(FixedUpdate, update_colliding_entities
+                .after(collision::detect_contacts)
+                .in_set(PhysicsSet::Collide))
+        ;
+    }
+}
+
+/// 今回の接触から重なっているエンティティを更新し、センサーの出入りのイベントを送る関数
+fn update_colliding_entities(
+    mut commands: Commands,
+    contacts: Res<Contacts>,
+    mut query: Query<(Entity, &mut CollidingEntities, Has<Sensor>)>,
+    mut entered_events: EventWriter<SensorEntered>,
+    mut stayed_events: EventWriter<SensorStayed>,
+    mut exited_events: EventWriter<SensorExited>,
+) {
+    info_once!("update_colliding_entities");
+
+    let mut current: EntityHashMap<EntityHashSet> = EntityHashMap::default();
+    for pair in contacts.iter() {
+        for (entity, other) in [(pair.entity1, pair.entity2), (pair.entity2, pair.entity1)] {
+            if query.contains(entity) {
+                current.entry(entity).or_default().insert(other);
+            }
+        }
+    }
+
+    for (sensor, mut colliding, is_sensor) in &mut query {
+        let entities = current.remove(&sensor).unwrap_or_default();
+        if is_sensor {
+            // ハッシュの順番に左右されないように、エンティティの順に送る
+            let mut entered: Vec<Entity> = entities.difference(&colliding).copied().collect();
+            let mut stayed: Vec<Entity> = entities.intersection(&colliding).copied().collect();
+            let mut exited: Vec<Entity> = colliding.difference(&entities).copied().collect();
+            entered.sort_unstable();
+            stayed.sort_unstable();
+            exited.sort_unstable();
+            for entity in entered {
+                let event = SensorEntered { sensor, entity };
+                entered_events.write(event);
+                commands.trigger_targets(event, sensor);
+            }
+            for entity in stayed {
+                let event = SensorStayed { sensor, entity };
+                stayed_events.write(event);
+                commands.trigger_targets(event, sensor);
+            }
+            for entity in exited {
+                let event = SensorExited { sensor, entity };
+                exited_events.write(event);
+                commands.trigger_targets(event, sensor);
+            }
+        }
+        colliding.set_if_neq(CollidingEntities(entities));
+    }
+}
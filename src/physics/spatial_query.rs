@@ -0,0 +1,291 @@
+use bevy::{
+    prelude::*,
+    ecs::system::SystemParam,
+    math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, RayCast2d},
+};
+
+use super::{BroadPhase, Collider, CollisionLayers, Position, Rotation, ccd, collider};
+
+/// 形状を移動させて当たった位置から、接触点と法線を調べるためにめり込ませる距離
+const SHAPECAST_SKIN: f32 = 0.05;
+
+/// 空間の問い合わせで対象にする形状の条件
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpatialQueryFilter {
+    /// 対象にするグループ
+    /// 形状の`membership`と1つでも重なれば対象になる
+    pub mask: u32,
+    /// 対象から外すエンティティ
+    pub excluded: Vec<Entity>,
+}
+
+impl Default for SpatialQueryFilter {
+    fn default() -> Self {
+        Self::from_mask(CollisionLayers::ALL)
+    }
+}
+
+impl SpatialQueryFilter {
+    /// 対象にするグループを指定して条件を作成する関数
+    pub fn from_mask(mask: u32) -> Self {
+        Self {
+            mask,
+            excluded: Vec::new(),
+        }
+    }
+
+    /// 対象から外すエンティティを追加する関数
+    pub fn with_excluded(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        self.excluded.extend(entities);
+        self
+    }
+
+    /// 形状が対象になるかを返す関数
+    fn accepts(&self, entity: Entity, layers: &CollisionLayers) -> bool {
+        self.mask & layers.membership != 0 && !self.excluded.contains(&entity)
+    }
+}
+
+/// 光線が当たった形状の情報
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub entity: Entity,
+    /// 光線の始点から当たった点までの距離
+    pub distance: f32,
+    /// 当たった点
+    pub point: Vec2,
+    /// 当たった面の外向きの法線
+    pub normal: Vec2,
+}
+
+/// 移動させた形状が当たった形状の情報
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeHit {
+    pub entity: Entity,
+    /// 当たるまでに移動した距離
+    pub distance: f32,
+    /// 接触点
+    pub point: Vec2,
+    /// 当たった面の外向きの法線
+    pub normal: Vec2,
+}
+
+/// 形状に対して光線や点などで問い合わせるシステムパラメーター
+/// 広域判定の空間ハッシュで候補を絞り込むので、直前の固定タイムステップの時点の形状が対象になる
+#[derive(SystemParam)]
+pub struct SpatialQuery<'w, 's> {
+    broad_phase: Res<'w, BroadPhase>,
+    colliders: Query<'w, 's, (&'static Collider, &'static Position, Option<&'static Rotation>)>,
+}
+
+impl SpatialQuery<'_, '_> {
+    /// 光線を飛ばして、最初に当たった形状を返す関数
+    /// 始点が形状の内側にある場合は、距離0で当たったことになる
+    pub fn raycast(
+        &self,
+        origin: Vec2,
+        direction: Dir2,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<RayHit> {
+        let end = origin + direction * max_distance;
+        let bounds = Aabb2d::from_point_cloud(Isometry2d::IDENTITY, &[origin, end]);
+        self.candidates(&bounds, filter)
+            .filter_map(|(entity, collider, isometry)| {
+                let (distance, normal) = cast_ray(&collider, isometry, origin, direction, max_distance)?;
+                Some(RayHit {
+                    entity,
+                    distance,
+                    point: origin + direction * distance,
+                    normal,
+                })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// 形状を移動させて、最初に当たった形状を返す関数
+    /// 当たる時刻は連続的な衝突判定と同じく、円か境界ボックスで近似して求める
+    pub fn shapecast(
+        &self,
+        shape: &Collider,
+        isometry: Isometry2d,
+        direction: Dir2,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<ShapeHit> {
+        let motion = direction * max_distance;
+        let start = shape.aabb(isometry);
+        let end = Aabb2d::new(start.center() + motion, start.half_size());
+        self.candidates(&start.merge(&end), filter)
+            .filter_map(|(entity, collider, other_isometry)| {
+                // 最初から重なっている場合は、移動せずに当たる
+                let distance = match shape.contact(isometry, &collider, other_isometry) {
+                    Some(_) => 0.0,
+                    None => ccd::time_of_impact(shape, isometry, motion, &collider, other_isometry, Vec2::ZERO)?
+                        * max_distance,
+                };
+                let moved = Isometry2d::new(
+                    isometry.translation + direction * (distance + SHAPECAST_SKIN),
+                    isometry.rotation,
+                );
+                let (point, normal) = shape.contact(moved, &collider, other_isometry)
+                    .map_or((moved.translation, -*direction), |contact| (contact.point, -contact.normal));
+                Some(ShapeHit { entity, distance, point, normal })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// 点を含む形状を返す関数
+    pub fn point_query(&self, point: Vec2, filter: &SpatialQueryFilter) -> Vec<Entity> {
+        let bounds = Aabb2d::new(point, Vec2::ZERO);
+        let probe = Collider::circle(0.0);
+        self.candidates(&bounds, filter)
+            .filter(|(_, collider, isometry)| {
+                probe.contact(Isometry2d::from_translation(point), collider, *isometry).is_some()
+            })
+            .map(|(entity, ..)| entity)
+            .collect()
+    }
+
+    /// 境界ボックスと重なる形状を返す関数
+    pub fn aabb_query(&self, aabb: Aabb2d, filter: &SpatialQueryFilter) -> Vec<Entity> {
+        let probe = Collider::rectangle(aabb.half_size().x * 2.0, aabb.half_size().y * 2.0);
+        self.candidates(&aabb, filter)
+            .filter(|(_, collider, isometry)| {
+                probe.contact(Isometry2d::from_translation(aabb.center()), collider, *isometry).is_some()
+            })
+            .map(|(entity, ..)| entity)
+            .collect()
+    }
+
+    /// 広域判定で範囲と重なる、条件に合う形状を返す関数
+    fn candidates<'a>(
+        &'a self,
+        bounds: &Aabb2d,
+        filter: &'a SpatialQueryFilter,
+    ) -> impl Iterator<Item = (Entity, Collider, Isometry2d)> + 'a {
+        self.broad_phase.query_aabb(bounds)
+            .into_iter()
+            .filter(|(entity, _, layers)| filter.accepts(*entity, layers))
+            .filter_map(|(entity, ..)| {
+                let (collider, position, rotation) = self.colliders.get(entity).ok()?;
+                Some((entity, *collider, collider::isometry(position, rotation)))
+            })
+    }
+}
+
+/// 光線が形状に当たる距離と、当たった面の法線を返す関数
+fn cast_ray(
+    collider: &Collider,
+    isometry: Isometry2d,
+    origin: Vec2,
+    direction: Dir2,
+    max_distance: f32,
+) -> Option<(f32, Vec2)> {
+    // 回転しない矩形は位置だけを使う
+    let isometry = match collider {
+        Collider::Aabb(_) => Isometry2d::from_translation(isometry.translation),
+        _ => isometry,
+    };
+    // 形状のローカル座標で考える
+    let inverse = isometry.inverse();
+    let local_origin = inverse * origin;
+    let local_direction = inverse.rotation * direction;
+    let ray = RayCast2d::new(local_origin, local_direction, max_distance);
+    let point_at = |distance: f32| local_origin + local_direction * distance;
+
+    let (distance, normal) = match collider {
+        Collider::Circle(circle) => {
+            let distance = ray.circle_intersection_at(&BoundingCircle::new(Vec2::ZERO, circle.radius))?;
+            (distance, point_at(distance).normalize_or_zero())
+        }
+        Collider::Aabb(rectangle) | Collider::Obb(rectangle) => {
+            let distance = ray.aabb_intersection_at(&Aabb2d::new(Vec2::ZERO, rectangle.half_size))?;
+            (distance, box_normal(point_at(distance), rectangle.half_size))
+        }
+        Collider::Capsule(capsule) => {
+            // 両端の円と、中央の矩形のうち最も近いもの
+            let ends = [capsule.half_length, -capsule.half_length].map(|y| {
+                let center = Vec2::new(0.0, y);
+                ray.circle_intersection_at(&BoundingCircle::new(center, capsule.radius))
+                    .map(|distance| (distance, (point_at(distance) - center).normalize_or_zero()))
+            });
+            let half_size = Vec2::new(capsule.radius, capsule.half_length);
+            let middle = ray.aabb_intersection_at(&Aabb2d::new(Vec2::ZERO, half_size))
+                .map(|distance| (distance, box_normal(point_at(distance), half_size)));
+            ends.into_iter().chain([middle]).flatten().min_by(|a, b| a.0.total_cmp(&b.0))?
+        }
+        Collider::Segment(segment) => {
+            let [start, end] = segment.vertices;
+            ray_segment(local_origin, local_direction, max_distance, start, end)?
+        }
+        Collider::Polygon(polygon) => ray_polygon(local_origin, local_direction, max_distance, polygon.vertices())?,
+    };
+
+    // 始点が内側にある場合は、光線の向きと逆向きを法線にする
+    if distance <= 0.0 {
+        return Some((0.0, -*direction));
+    }
+    Some((distance, isometry.rotation * normal))
+}
+
+/// 矩形の表面の点から、その面の外向きの法線を返す関数
+fn box_normal(point: Vec2, half_size: Vec2) -> Vec2 {
+    let scaled = point / half_size;
+    if scaled.x.abs() > scaled.y.abs() {
+        Vec2::new(scaled.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, scaled.y.signum())
+    }
+}
+
+/// 光線が線分に当たる距離と、光線の側を向いた線分の法線を返す関数
+fn ray_segment(origin: Vec2, direction: Dir2, max_distance: f32, start: Vec2, end: Vec2) -> Option<(f32, Vec2)> {
+    let edge = end - start;
+    let denominator = direction.perp_dot(edge);
+    // 平行な場合は当たらないものとする
+    if denominator == 0.0 {
+        return None;
+    }
+    let offset = start - origin;
+    let distance = offset.perp_dot(edge) / denominator;
+    let along = offset.perp_dot(*direction) / denominator;
+    if !(0.0..=max_distance).contains(&distance) || !(0.0..=1.0).contains(&along) {
+        return None;
+    }
+    let normal = edge.perp().normalize_or_zero();
+    let normal = if normal.dot(*direction) > 0.0 { -normal } else { normal };
+    Some((distance, normal))
+}
+
+/// 光線が反時計回りの凸多角形に当たる距離と、当たった辺の外向きの法線を返す関数
+/// 各辺の外側の半平面で光線を切り取り、入る距離のうち最も遠いものが当たる距離になる
+fn ray_polygon(origin: Vec2, direction: Dir2, max_distance: f32, vertices: &[Vec2]) -> Option<(f32, Vec2)> {
+    let (mut enter, mut exit, mut normal) = (0.0, max_distance, Vec2::ZERO);
+    for (i, start) in vertices.iter().enumerate() {
+        let end = vertices[(i + 1) % vertices.len()];
+        let outward = -(end - *start).perp().normalize_or_zero();
+        let approach = outward.dot(*direction);
+        let distance = outward.dot(*start - origin);
+        if approach == 0.0 {
+            // 辺と平行な場合は、外側にあれば当たらない
+            if distance < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let time = distance / approach;
+        if approach < 0.0 {
+            if time > enter {
+                enter = time;
+                normal = outward;
+            }
+        } else {
+            exit = exit.min(time);
+        }
+        if enter > exit {
+            return None;
+        }
+    }
+    Some((enter, normal))
+}
@@ -0,0 +1,168 @@
+use bevy::{
+    prelude::*,
+    ecs::{component::HookContext, world::DeferredWorld},
+    window::{PrimaryWindow, WindowResized},
+};
+
+use crate::WINDOW_SIZE;
+use super::{
+    Collider,
+    CollisionStarted,
+    PhysicsSet,
+    Position,
+    PreviousPosition,
+    Rotation,
+    WallHit,
+    WallSide,
+    collider,
+    events,
+};
+
+/// 画面端の壁の厚さ
+/// 速い形状が通り抜けないように、画面の外側へ厚く作る
+const WALL_THICKNESS: f32 = 100.0;
+
+/// 壁で囲むプレイ範囲を管理するリソース
+/// 原点を中心とし、メインのウィンドウの大きさが変わると追従する
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct PlayArea {
+    pub size: Vec2,
+}
+
+impl Default for PlayArea {
+    fn default() -> Self {
+        Self::new(WINDOW_SIZE)
+    }
+}
+
+impl PlayArea {
+    /// 大きさを指定してプレイ範囲を作成する関数
+    pub fn new(size: Vec2) -> Self {
+        Self { size }
+    }
+
+    /// 指定した画面端の壁の中心と大きさを返す関数
+    /// 左右の壁は、角の隙間を埋めるように上下へ伸ばす
+    pub fn wall(&self, side: WallSide) -> (Vec2, Vec2) {
+        let half_size = self.size / 2.0;
+        let offset = half_size + WALL_THICKNESS / 2.0;
+        let vertical = Vec2::new(WALL_THICKNESS, self.size.y + WALL_THICKNESS * 2.0);
+        let horizontal = Vec2::new(self.size.x, WALL_THICKNESS);
+        match side {
+            WallSide::Left => (Vec2::new(-offset.x, 0.0), vertical),
+            WallSide::Right => (Vec2::new(offset.x, 0.0), vertical),
+            WallSide::Top => (Vec2::new(0.0, offset.y), horizontal),
+            WallSide::Bottom => (Vec2::new(0.0, -offset.y), horizontal),
+        }
+    }
+}
+
+/// プレイ範囲の端に沿って4つの壁を生成するコンポーネント
+/// 追加されたときに、プレイ範囲の外側に接する矩形の壁を子エンティティとして生成する
+/// 壁は原点を中心としたプレイ範囲に沿って置かれるので、このエンティティは原点に置く
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+#[component(on_add = spawn_walls)]
+#[require(Transform, Visibility)]
+pub struct WindowBounds;
+
+/// プレイ範囲をウィンドウに合わせ、画面端の壁に当たったことをイベントで知らせるプラグイン
+pub struct WallPlugin;
+
+impl Plugin for WallPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PlayArea>()
+            // ウィンドウの無いヘッドレスモードでも読めるように登録しておく
+            .add_event::<WindowResized>()
+            .add_systems(PreUpdate, update_play_area)
+            .add_systems(FixedUpdate, (
+                resize_walls
+                    .run_if(resource_changed::<PlayArea>)
+                    .before(PhysicsSet::Integrate),
+                send_wall_hits
+                    .after(events::send_collision_events)
+                    .in_set(PhysicsSet::Collide),
+            ))
+        ;
+    }
+}
+
+/// 画面端の壁を子エンティティとして生成する関数
+fn spawn_walls(mut world: DeferredWorld, context: HookContext) {
+    let play_area = world.get_resource::<PlayArea>().copied().unwrap_or_default();
+    world.commands().entity(context.entity).with_children(|parent| {
+        for side in WallSide::ALL {
+            let (center, size) = play_area.wall(side);
+            parent.spawn((
+                side,
+                Collider::rectangle(size.x, size.y),
+                Transform::from_translation(center.extend(0.0)),
+            ));
+        }
+    });
+}
+
+/// メインのウィンドウの大きさをプレイ範囲に反映する関数
+fn update_play_area(
+    mut resized_events: EventReader<WindowResized>,
+    windows: Query<Ref<Window>, With<PrimaryWindow>>,
+    mut play_area: ResMut<PlayArea>,
+) {
+    info_once!("update_play_area");
+
+    // ウィンドウが作られたときは、その大きさに合わせる
+    for window in windows.iter().filter(|window| window.is_added()) {
+        play_area.set_if_neq(PlayArea::new(window.size()));
+    }
+    for resized in resized_events.read() {
+        if windows.contains(resized.window) {
+            debug!("play area resized to {}x{}", resized.width, resized.height);
+            play_area.set_if_neq(PlayArea::new(Vec2::new(resized.width, resized.height)));
+        }
+    }
+}
+
+/// プレイ範囲に合わせて画面端の壁を動かす関数
+fn resize_walls(
+    play_area: Res<PlayArea>,
+    mut walls: Query<(&WallSide, &mut Collider, &mut Position, &mut PreviousPosition)>,
+) {
+    info_once!("resize_walls");
+
+    for (side, mut wall_collider, mut position, mut previous) in &mut walls {
+        let (center, size) = play_area.wall(*side);
+        *wall_collider = Collider::rectangle(size.x, size.y);
+        // 補間で壁が滑らかに動かないように、前回の位置も合わせる
+        **position = center;
+        **previous = center;
+    }
+}
+
+/// 画面端の壁と触れ始めた形状に、壁に当たったイベントを送る関数
+fn send_wall_hits(
+    mut commands: Commands,
+    mut started_events: EventReader<CollisionStarted>,
+    walls: Query<(&WallSide, &Collider, &Position, Option<&Rotation>)>,
+    mut wall_hits: EventWriter<WallHit>,
+) {
+    info_once!("send_wall_hits");
+
+    for started in started_events.read() {
+        for (wall, entity) in [(started.entity1, started.entity2), (started.entity2, started.entity1)] {
+            let Ok((side, wall_collider, position, rotation)) = walls.get(wall) else {
+                continue;
+            };
+            // 接触点を壁の内側の面の上に移す
+            let aabb = wall_collider.aabb(collider::isometry(position, rotation));
+            let point = match side {
+                WallSide::Left => started.point.with_x(aabb.max.x),
+                WallSide::Right => started.point.with_x(aabb.min.x),
+                WallSide::Top => started.point.with_y(aabb.min.y),
+                WallSide::Bottom => started.point.with_y(aabb.max.y),
+            };
+            let event = WallHit { entity, side: *side, point, normal: side.normal() };
+            wall_hits.write(event);
+            commands.trigger_targets(event, entity);
+        }
+    }
+}
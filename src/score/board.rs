@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+
+use super::ScoreSource;
+
+/// 全角の数字の`０`
+const FULL_WIDTH_ZERO: u32 = '０' as u32;
+/// 区切り文字を入れる桁数
+const DIGIT_GROUP: usize = 3;
+
+/// 名前の付いたスコアボード
+/// 名前を指定した`ScoreEvent`と、`sources`に含まれる発生源の名前の無い`ScoreEvent`で得点が加算される
+/// このコンポーネントが無い`Score`は、名前の無い全ての`ScoreEvent`で加算される
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct Scoreboard {
+    pub name: String,
+    pub sources: Vec<ScoreSource>,
+}
+
+impl Scoreboard {
+    /// 名前を指定して、名前を指定したイベントだけで加算されるスコアボードを作成する関数
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sources: Vec::new(),
+        }
+    }
+
+    /// 名前の無いイベントで加算される発生源を追加する関数
+    pub fn with_sources(mut self, sources: impl IntoIterator<Item = ScoreSource>) -> Self {
+        self.sources.extend(sources);
+        self
+    }
+
+    /// 得点のイベントを受け取るかを返す関数
+    pub fn accepts(&self, board: Option<&str>, source: &ScoreSource) -> bool {
+        match board {
+            Some(name) => name == self.name,
+            None => self.sources.contains(source),
+        }
+    }
+}
+
+/// 得点の表示の書式
+/// `Score`と同じエンティティに追加すると、`TextSpan`がこの書式で表示される
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScoreFormat {
+    /// 3桁ごとに入れる区切り文字
+    pub separator: Option<char>,
+    /// 0で埋める桁数（時間の場合は分の桁数）
+    pub min_digits: usize,
+    /// 全角の数字で表示するか
+    pub full_width: bool,
+    /// 得点を秒数として、分:秒で表示するか
+    pub time: bool,
+}
+
+impl ScoreFormat {
+    /// 秒数を分:秒で表示する書式を作成する関数
+    pub fn time() -> Self {
+        Self {
+            min_digits: 2,
+            time: true,
+            ..Default::default()
+        }
+    }
+
+    /// 3桁ごとに区切り文字を入れるように変更する関数
+    pub fn with_separator(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// 指定した桁数まで0で埋めるように変更する関数
+    pub fn zero_padded(mut self, digits: usize) -> Self {
+        self.min_digits = digits;
+        self
+    }
+
+    /// 全角の数字で表示するように変更する関数
+    pub fn full_width(mut self) -> Self {
+        self.full_width = true;
+        self
+    }
+
+    /// 得点を書式に従って文字列にする関数
+    pub fn format(&self, value: usize) -> String {
+        let text = if self.time {
+            format!("{:0width$}:{:02}", value / 60, value % 60, width = self.min_digits)
+        } else {
+            let digits = format!("{value:0width$}", width = self.min_digits);
+            match self.separator {
+                Some(separator) => group_digits(&digits, separator),
+                None => digits,
+            }
+        };
+        if self.full_width {
+            text.chars().map(to_full_width).collect()
+        } else {
+            text
+        }
+    }
+}
+
+/// 右から3桁ごとに区切り文字を入れる関数
+fn group_digits(digits: &str, separator: char) -> String {
+    let mut grouped = String::with_capacity(digits.len() * 2);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(DIGIT_GROUP) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// 数字と記号を全角にする関数
+fn to_full_width(character: char) -> char {
+    match character {
+        '0'..='9' => char::from_u32(FULL_WIDTH_ZERO + (character as u32 - '0' as u32)).unwrap_or(character),
+        ':' => '：',
+        ',' => '，',
+        '.' => '．',
+        other => other,
+    }
+}
+
+/// スコアボードを並べる画面の端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScreenEdge {
+    TopLeft,
+    Top,
+    TopRight,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ScreenEdge {
+    /// 画面の端に固定し、子のスコアボードを縦に並べるノードを返す関数
+    pub fn node(self, padding: f32) -> Node {
+        let inset = Val::Px(padding);
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(padding / 2.0),
+            ..Default::default()
+        };
+        match self {
+            ScreenEdge::TopLeft | ScreenEdge::Top | ScreenEdge::TopRight => node.top = inset,
+            ScreenEdge::BottomLeft | ScreenEdge::Bottom | ScreenEdge::BottomRight => node.bottom = inset,
+        }
+        match self {
+            ScreenEdge::TopLeft | ScreenEdge::BottomLeft => {
+                node.left = inset;
+                node.align_items = AlignItems::FlexStart;
+            }
+            ScreenEdge::TopRight | ScreenEdge::BottomRight => {
+                node.right = inset;
+                node.align_items = AlignItems::FlexEnd;
+            }
+            ScreenEdge::Top | ScreenEdge::Bottom => {
+                node.left = Val::Px(0.0);
+                node.right = Val::Px(0.0);
+                node.align_items = AlignItems::Center;
+            }
+        }
+        node
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+
+use super::{Score, ScoreAwarded, ScoreFormat};
+
+/// 表示中の得点が本当の得点に近づく速さ（1秒あたりの差の割合の指数）
+const ROLL_RATE: f32 = 10.0;
+/// 表示中の得点が1秒あたりに最低限進む量
+const MIN_ROLL_SPEED: f32 = 20.0;
+/// 得点が変わったときに文字を大きくする割合
+const PULSE_SCALE: f32 = 0.3;
+/// 大きくした文字が元に戻るまでの秒数
+const PULSE_DURATION: f32 = 0.25;
+/// ポップアップが上に移動する速さ
+const POPUP_RISE_SPEED: f32 = 40.0;
+/// ポップアップが消えるまでの秒数
+const POPUP_LIFETIME: f32 = 1.0;
+const POPUP_Z: f32 = 10.0;
+
+/// 得点の表示を、本当の得点に向かって少しずつ数え上げるコンポーネント
+/// `Score`と`TextSpan`と同じエンティティに追加する
+/// 得点が変わると、文字を一瞬だけ大きくする
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct RollingScore {
+    /// 表示中の得点
+    pub displayed: f32,
+    /// 元の文字の大きさ
+    pub font_size: f32,
+    /// 文字を大きくしている残りの割合（0.0から1.0）
+    pub pulse: f32,
+}
+
+impl RollingScore {
+    /// 元の文字の大きさを指定して作成する関数
+    pub fn new(font_size: f32) -> Self {
+        Self {
+            displayed: 0.0,
+            font_size,
+            pulse: 0.0,
+        }
+    }
+
+    /// 表示中の得点を整数で返す関数
+    pub fn value(&self) -> usize {
+        self.displayed.round() as usize
+    }
+}
+
+/// 得点した位置に表示され、上に移動しながら消える「+N」のテキスト
+#[derive(Component, Debug)]
+pub struct ScorePopup {
+    pub timer: Timer,
+}
+
+/// ポップアップの文字の設定を管理するリソース
+#[derive(Resource, Debug, Clone)]
+pub struct ScorePopupStyle {
+    pub font: TextFont,
+    pub color: Color,
+}
+
+impl Default for ScorePopupStyle {
+    fn default() -> Self {
+        Self {
+            font: TextFont::default(),
+            color: Color::WHITE,
+        }
+    }
+}
+
+/// 数え上げずに表示する、変わった得点の条件
+type ChangedStaticScore = (Changed<Score>, Without<RollingScore>);
+
+/// 得点が変わったら、表示中の得点を数え上げずにそのまま表示する関数
+pub(super) fn update_score_text(
+    mut query: Query<(&Score, &mut TextSpan, Option<&ScoreFormat>), ChangedStaticScore>,
+) {
+    for (score, mut span, format) in &mut query {
+        **span = format_score(**score, format);
+    }
+}
+
+/// 得点を書式に従って文字列にする関数
+fn format_score(value: usize, format: Option<&ScoreFormat>) -> String {
+    format.map_or_else(|| format!("{value}"), |format| format.format(value))
+}
+
+/// 数え上げて表示する得点のコンポーネント
+type RollingScoreData = (
+    Ref<'static, Score>,
+    &'static mut RollingScore,
+    &'static mut TextSpan,
+    Option<&'static ScoreFormat>,
+    Option<&'static mut TextFont>,
+);
+
+/// 表示中の得点を本当の得点に近づけ、得点が変わったら文字を大きくする関数
+pub(super) fn roll_score_text(
+    time: Res<Time>,
+    mut query: Query<RollingScoreData>,
+) {
+    info_once!("roll_score_text");
+
+    let delta = time.delta_secs();
+    for (score, mut rolling, mut span, format, font) in &mut query {
+        if score.is_changed() && !score.is_added() {
+            rolling.pulse = 1.0;
+        }
+
+        // 差が大きいほど速く、小さくても最低限の速さで近づける
+        let target = **score as f32;
+        let difference = target - rolling.displayed;
+        if difference != 0.0 {
+            let step = (difference.abs() * (1.0 - (-ROLL_RATE * delta).exp())).max(MIN_ROLL_SPEED * delta);
+            rolling.displayed = if step >= difference.abs() {
+                target
+            } else {
+                rolling.displayed + step * difference.signum()
+            };
+        }
+        let text = format_score(rolling.value(), format);
+        if span.0 != text {
+            span.0 = text;
+        }
+
+        rolling.pulse = (rolling.pulse - delta / PULSE_DURATION).max(0.0);
+        if let Some(mut font) = font {
+            let font_size = rolling.font_size * (1.0 + PULSE_SCALE * rolling.pulse);
+            if font.font_size != font_size {
+                font.font_size = font_size;
+            }
+        }
+    }
+}
+
+/// 位置のある得点が加算されたら、その位置にポップアップを生成する関数
+pub(super) fn spawn_score_popups(
+    mut commands: Commands,
+    mut events: EventReader<ScoreAwarded>,
+    style: Res<ScorePopupStyle>,
+) {
+    info_once!("spawn_score_popups");
+
+    for event in events.read() {
+        let Some(position) = event.position else {
+            continue;
+        };
+        commands.spawn((
+            Text2d::new(format!("+{}", event.points)),
+            style.font.clone(),
+            TextColor(style.color),
+            Transform::from_translation(position.extend(POPUP_Z)),
+            ScorePopup { timer: Timer::from_seconds(POPUP_LIFETIME, TimerMode::Once) },
+        ));
+    }
+}
+
+/// ポップアップを上に移動させながら透明にし、時間が過ぎたら削除する関数
+pub(super) fn animate_score_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut TextColor)>,
+) {
+    info_once!("animate_score_popups");
+
+    for (entity, mut popup, mut transform, mut color) in &mut query {
+        popup.timer.tick(time.delta());
+        if popup.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.y += POPUP_RISE_SPEED * time.delta_secs();
+        color.0.set_alpha(popup.timer.fraction_remaining());
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::storage;
+
+/// ハイスコアに残す件数
+pub const MAX_HIGH_SCORES: usize = 10;
+/// ハイスコアを保存するファイル名
+pub const HIGH_SCORES_FILE: &str = "high_scores.ron";
+/// 読み込めなかったハイスコアのファイルに付ける拡張子
+pub const BACKUP_EXTENSION: &str = ".bak";
+/// なまえの最大の文字数
+pub const MAX_NAME_LENGTH: usize = 8;
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// ハイスコアの1件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: usize,
+    /// 記録した日付（UTCのYYYY-MM-DD）
+    pub date: String,
+}
+
+impl HighScoreEntry {
+    /// 今日の日付で記録を作成する関数
+    pub fn new(name: impl Into<String>, score: usize) -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Self {
+            name: name.into(),
+            score,
+            date: format_date(seconds),
+        }
+    }
+}
+
+/// ハイスコアの読み込みや保存に失敗したときのエラー
+#[derive(Debug, Error)]
+pub enum HighScoresError {
+    #[error("could not access high scores file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse high scores file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("could not serialize high scores: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// スコアの高い順に上位の記録を管理するリソース
+/// 保存先が無い場合は、保存せずにメモリ上だけで管理する
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct HighScores {
+    entries: Vec<HighScoreEntry>,
+    capacity: usize,
+    path: Option<PathBuf>,
+}
+
+impl FromWorld for HighScores {
+    /// データフォルダ（`StorageRoot`があればその中）のファイルから読み込む
+    /// 読み込めない場合は警告を出し、空の記録から始める
+    /// 読み込めなかったファイルは上書きしないように`*.ron.bak`に移し、移せない場合は保存しない
+    fn from_world(world: &mut World) -> Self {
+        let Some(path) = storage::world_data_dir(world).map(|dir| dir.join(HIGH_SCORES_FILE)) else {
+            warn!("data directory not found; high scores will not be saved");
+            return Self::new(MAX_HIGH_SCORES, None);
+        };
+        Self::load(&path, MAX_HIGH_SCORES).unwrap_or_else(|error| {
+            let backup = backup_path(&path);
+            match fs::rename(&path, &backup) {
+                Ok(()) => {
+                    warn!("{error}; moved it to {} and starting with empty high scores", backup.display());
+                    Self::new(MAX_HIGH_SCORES, Some(path))
+                }
+                Err(rename_error) => {
+                    warn!("{error}; could not move it aside ({rename_error}), so high scores will not be saved");
+                    Self::new(MAX_HIGH_SCORES, None)
+                }
+            }
+        })
+    }
+}
+
+impl HighScores {
+    /// 件数と保存先を指定して、空の記録を作成する関数
+    pub fn new(capacity: usize, path: Option<PathBuf>) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+            path,
+        }
+    }
+
+    /// ファイルから記録を読み込む関数
+    /// ファイルが存在しない場合は空の記録を返す
+    pub fn load(path: &Path, capacity: usize) -> Result<Self, HighScoresError> {
+        let mut high_scores = Self::new(capacity, Some(path.to_path_buf()));
+        match fs::read(path) {
+            Ok(bytes) => {
+                let mut entries: Vec<HighScoreEntry> = ron::de::from_bytes(&bytes)?;
+                // 手で書き換えられていても、スコアの高い順に件数分だけ残す
+                entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+                entries.truncate(capacity);
+                high_scores.entries = entries;
+                Ok(high_scores)
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(high_scores),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// 記録をファイルに保存する関数
+    /// 保存先が無い場合は何もしない
+    pub fn save(&self) -> Result<(), HighScoresError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = ron::ser::to_string_pretty(&self.entries, ron::ser::PrettyConfig::default())?;
+        fs::write(path, text)?;
+        debug!("saved high scores to {}", path.display());
+        Ok(())
+    }
+
+    /// スコアの高い順に並んだ記録を返す関数
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    /// 保存先を返す関数
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// スコアが記録に残るかを返す関数
+    /// 0点と、最下位の記録以下のスコアは残らない
+    pub fn qualifies(&self, score: usize) -> bool {
+        score > 0 && (self.entries.len() < self.capacity || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// 記録を追加し、順位（0から）を返す関数
+    /// 同じスコアの場合は先に記録した方が上位になる。記録に残らない場合は`None`を返す
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.entries.partition_point(|other| other.score >= entry.score);
+        self.entries.insert(rank, entry);
+        self.entries.truncate(self.capacity);
+        Some(rank)
+    }
+}
+
+/// 読み込めなかったハイスコアのファイルを残しておく場所を返す関数
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(BACKUP_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// UNIX時間の秒数をUTCの日付（YYYY-MM-DD）にする関数
+pub fn format_date(seconds: u64) -> String {
+    // 1970-01-01からの日数を、3月始まりの400年周期で暦に直す
+    let days = (seconds / SECONDS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
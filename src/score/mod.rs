@@ -0,0 +1,20 @@
+pub mod board;
+pub mod display;
+pub mod high_scores;
+pub mod rules;
+pub mod scoring;
+
+pub use board::{ScoreFormat, ScreenEdge, Scoreboard};
+pub use display::{RollingScore, ScorePopup, ScorePopupStyle};
+pub use high_scores::{HighScoreEntry, HighScores, HighScoresError};
+pub use rules::{BonusRule, MAX_MULTIPLIER_LIMIT, ScoreRules, ScoreRulesError, ScoreRulesLoader};
+pub use scoring::{
+    Score,
+    ScoreAwarded,
+    ScoreEvent,
+    ScoreMultiplier,
+    ScorePlugin,
+    ScoreSet,
+    ScoreSource,
+    add_score,
+};
@@ -0,0 +1,147 @@
+use bevy::{
+    prelude::*,
+    asset::{AssetLoader, LoadContext, io::Reader},
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::ScoreSource;
+
+/// 得点のルールの設定ファイル
+pub const PATH_SCORE_RULES: &str = "config/score.rules.ron";
+/// 設定できる倍率の上限の最大値
+pub const MAX_MULTIPLIER_LIMIT: u32 = 100;
+
+/// 得点の倍率やボーナスのルール（RON形式）
+/// 書かれていない項目は既定値が使われる
+#[derive(Asset, Resource, TypePath, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoreRules {
+    /// 次の得点までにこの秒数が過ぎると、コンボが減る
+    pub combo_window: f32,
+    /// 倍率が1つ上がるのに必要なコンボ数
+    pub combo_step: u32,
+    /// 倍率の上限
+    pub max_multiplier: u32,
+    /// コンボが途切れたときに、`combo_window`ごとに減るコンボ数
+    pub combo_decay: u32,
+    /// コンボ数に応じて加算されるボーナス
+    pub bonuses: Vec<BonusRule>,
+}
+
+impl Default for ScoreRules {
+    fn default() -> Self {
+        Self {
+            combo_window: 1.5,
+            combo_step: 5,
+            max_multiplier: 4,
+            combo_decay: 5,
+            bonuses: Vec::new(),
+        }
+    }
+}
+
+/// コンボ数が`every_combo`の倍数に達するたびに、`points`を加算するボーナス
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BonusRule {
+    /// 対象にする得点の発生源（省略すると全て）
+    #[serde(default)]
+    pub source: Option<ScoreSource>,
+    pub every_combo: u32,
+    pub points: usize,
+}
+
+impl BonusRule {
+    /// 得点の発生源とコンボ数から、ボーナスが加算されるかを返す関数
+    pub fn applies(&self, source: &ScoreSource, combo: u32) -> bool {
+        self.source.as_ref().is_none_or(|rule_source| rule_source == source)
+            && combo > 0
+            && combo.is_multiple_of(self.every_combo)
+    }
+}
+
+/// 得点のルールの読み込みや検証に失敗したときのエラー
+#[derive(Debug, Error)]
+pub enum ScoreRulesError {
+    #[error("could not read score rules: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse score rules: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid value for `{field}`: {value} ({reason})")]
+    Invalid {
+        field: &'static str,
+        value: String,
+        reason: &'static str,
+    },
+}
+
+impl ScoreRules {
+    /// RON形式の文字列からルールを読み込み、検証する関数
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, ScoreRulesError> {
+        let rules: Self = ron::de::from_bytes(bytes)?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// 設定値が正しい範囲にあるかを検証する関数
+    pub fn validate(&self) -> Result<(), ScoreRulesError> {
+        let invalid = |field, value: String, reason| Err(ScoreRulesError::Invalid { field, value, reason });
+
+        if !(self.combo_window.is_finite() && self.combo_window > 0.0) {
+            return invalid("combo_window", format!("{}", self.combo_window), "must be positive");
+        }
+        if self.combo_step == 0 {
+            return invalid("combo_step", format!("{}", self.combo_step), "must be at least 1");
+        }
+        if self.max_multiplier == 0 {
+            return invalid("max_multiplier", format!("{}", self.max_multiplier), "must be at least 1");
+        }
+        if self.max_multiplier > MAX_MULTIPLIER_LIMIT {
+            return invalid("max_multiplier", format!("{}", self.max_multiplier), "must be at most 100");
+        }
+        if let Some(bonus) = self.bonuses.iter().find(|bonus| bonus.every_combo == 0) {
+            return invalid("bonuses", format!("{bonus:?}"), "`every_combo` must be at least 1");
+        }
+        Ok(())
+    }
+
+    /// コンボ数から得点の倍率を返す関数
+    pub fn multiplier(&self, combo: u32) -> u32 {
+        (1 + combo.saturating_sub(1) / self.combo_step).min(self.max_multiplier)
+    }
+
+    /// 得点の発生源とコンボ数から、加算されるボーナスの合計を返す関数
+    /// 合計が`usize`に収まらない場合は、最大値で止める
+    pub fn bonus(&self, source: &ScoreSource, combo: u32) -> usize {
+        self.bonuses.iter()
+            .filter(|bonus| bonus.applies(source, combo))
+            .fold(0, |total: usize, bonus| total.saturating_add(bonus.points))
+    }
+}
+
+/// 得点のルールをアセットとして読み込むローダー
+/// 検証に失敗した場合は読み込みエラーとなり、以前のルールがそのまま使われる
+#[derive(Default)]
+pub struct ScoreRulesLoader;
+
+impl AssetLoader for ScoreRulesLoader {
+    type Asset = ScoreRules;
+    type Settings = ();
+    type Error = ScoreRulesError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        ScoreRules::from_ron(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rules.ron"]
+    }
+}
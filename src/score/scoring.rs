@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::{ScoreRules, ScoreRulesLoader, Scoreboard, display::{self, ScorePopupStyle}};
+
+/// 得点を表示、管理するコンポーネント
+/// 同じエンティティに`TextSpan`があれば、得点が変わったときに表示も更新される
+/// `RollingScore`も追加すると、表示は少しずつ数え上げられる
+/// コンボはスコアごとに`ScoreMultiplier`で数える
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Deref, DerefMut)]
+#[require(ScoreMultiplier)]
+pub struct Score(pub usize);
+
+/// 得点の発生源
+/// 設定ファイルのボーナスのルールで、対象を絞り込むのに使う
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ScoreSource {
+    Input,
+    Collision,
+    Pickup,
+    Custom(String),
+}
+
+/// 得点を加算するイベント
+/// 倍率とボーナスは`ScorePlugin`が反映する
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ScoreEvent {
+    pub amount: usize,
+    pub source: ScoreSource,
+    /// 得点したワールド座標（あればポップアップを表示する）
+    pub position: Option<Vec2>,
+    /// 加算するスコアボードの名前（無ければ発生源で選ぶ）
+    pub board: Option<String>,
+}
+
+impl ScoreEvent {
+    /// 加算する得点と発生源を指定してイベントを作成する関数
+    pub fn new(amount: usize, source: ScoreSource) -> Self {
+        Self {
+            amount,
+            source,
+            position: None,
+            board: None,
+        }
+    }
+
+    /// 得点した位置を指定する関数
+    pub fn at(mut self, position: Vec2) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// 加算するスコアボードの名前を指定する関数
+    pub fn to(mut self, board: impl Into<String>) -> Self {
+        self.board = Some(board.into());
+        self
+    }
+}
+
+/// 倍率とボーナスを反映して、実際に加算された得点のイベント
+/// 得点を加算したスコアごとに送られ、どのスコアにも加算されなかった場合は送られない
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ScoreAwarded {
+    pub points: usize,
+    pub multiplier: u32,
+    pub combo: u32,
+    pub source: ScoreSource,
+    /// 得点したワールド座標
+    pub position: Option<Vec2>,
+    /// 加算したスコアボードの名前（名前の無いスコアなら`None`）
+    pub board: Option<String>,
+}
+
+/// 連続して得点したコンボ数と、途切れるまでの残り時間を管理するコンポーネント
+/// `Score`ごとに追加され、そのスコアに加算された得点だけを数える
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct ScoreMultiplier {
+    pub combo: u32,
+    /// 次にコンボが減るまでの秒数
+    pub remaining: f32,
+}
+
+impl ScoreMultiplier {
+    /// コンボ数から得点の倍率を返す関数
+    pub fn multiplier(&self, rules: &ScoreRules) -> u32 {
+        rules.multiplier(self.combo)
+    }
+
+    /// コンボ数と残り時間を元に戻す関数
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// 得点の計算を行うシステムセット
+/// `ScoreEvent`を送るシステムをこれより前に置くと、同じフレームで得点に反映される
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScoreSet;
+
+/// `ScoreEvent`を受け取り、コンボの倍率と設定ファイルのボーナスを反映して得点を加算するプラグイン
+/// 得点の表示の更新と、得点した位置のポップアップもここで行う
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<ScoreEvent>()
+            .add_event::<ScoreAwarded>()
+            .init_resource::<ScoreRules>()
+            .init_resource::<ScorePopupStyle>()
+            .init_asset::<ScoreRules>()
+            .init_asset_loader::<ScoreRulesLoader>()
+            .add_systems(Update, (
+                apply_score_rules,
+                decay_combo,
+                award_score,
+                display::update_score_text,
+                display::roll_score_text,
+                display::spawn_score_popups,
+                display::animate_score_popups,
+            ).chain().in_set(ScoreSet))
+        ;
+    }
+}
+
+/// 得点のプラグインを追加する関数
+/// 複数のサンプルから呼ばれても、1回だけ追加する
+pub fn add_score(app: &mut App) {
+    if !app.is_plugin_added::<ScorePlugin>() {
+        app.add_plugins(ScorePlugin);
+    }
+}
+
+/// 設定ファイルが読み込まれたり変更されたりしたら、ルールを反映する関数
+fn apply_score_rules(
+    mut events: EventReader<AssetEvent<ScoreRules>>,
+    assets: Res<Assets<ScoreRules>>,
+    mut rules: ResMut<ScoreRules>,
+) {
+    info_once!("apply_score_rules");
+
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        if let Some(loaded) = assets.get(*id) {
+            debug!("apply score rules: {:?}", loaded);
+            *rules = loaded.clone();
+        }
+    }
+}
+
+/// 得点しないまま時間が過ぎたら、コンボを減らす関数
+fn decay_combo(
+    time: Res<Time>,
+    rules: Res<ScoreRules>,
+    mut query: Query<&mut ScoreMultiplier>,
+) {
+    info_once!("decay_combo");
+
+    for mut multiplier in &mut query {
+        if multiplier.combo == 0 {
+            continue;
+        }
+        multiplier.remaining -= time.delta_secs();
+        while multiplier.combo > 0 && multiplier.remaining <= 0.0 {
+            multiplier.combo = multiplier.combo.saturating_sub(rules.combo_decay.max(1));
+            multiplier.remaining += rules.combo_window;
+            debug!("combo decayed: {}", multiplier.combo);
+        }
+    }
+}
+
+/// 得点のイベントを受け取るスコアと、そのコンボ
+type AwardTarget = (
+    &'static mut Score,
+    &'static mut ScoreMultiplier,
+    Option<&'static Scoreboard>,
+);
+
+/// 得点のイベントを受け取るスコアごとに、そのスコアのコンボの倍率とボーナスを反映して、得点を加算する関数
+/// どのスコアにも加算されないイベントは、コンボに数えない
+fn award_score(
+    mut events: EventReader<ScoreEvent>,
+    mut awarded: EventWriter<ScoreAwarded>,
+    mut query: Query<AwardTarget>,
+    rules: Res<ScoreRules>,
+) {
+    info_once!("award_score");
+
+    for event in events.read() {
+        let board = event.board.as_deref();
+        for (mut score, mut multiplier, scoreboard) in &mut query {
+            // 名前の無いスコアには、名前の無いイベントだけを加算する
+            let accepts = scoreboard.map_or(board.is_none(), |scoreboard| scoreboard.accepts(board, &event.source));
+            if !accepts {
+                continue;
+            }
+            multiplier.combo = multiplier.combo.saturating_add(1);
+            multiplier.remaining = rules.combo_window;
+            let combo = multiplier.combo;
+            let rate = multiplier.multiplier(&rules);
+            // 大きな得点でもパニックしたり桁あふれしたりせず、最大値で止める
+            let points = event.amount
+                .saturating_mul(rate as usize)
+                .saturating_add(rules.bonus(&event.source, combo));
+            **score = score.saturating_add(points);
+
+            let name = scoreboard.map(|scoreboard| scoreboard.name.clone());
+            debug!("score +{points} to {name:?} ({:?}, combo {combo}, x{rate})", event.source);
+            awarded.write(ScoreAwarded {
+                points,
+                multiplier: rate,
+                combo,
+                source: event.source.clone(),
+                position: event.position,
+                board: name,
+            });
+        }
+    }
+}
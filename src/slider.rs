@@ -0,0 +1,138 @@
+use bevy::{
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+
+const TRACK_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+const FILL_COLOR: Color = Color::srgb(0.5, 0.5, 1.0);
+const HANDLE_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const HANDLE_WIDTH: f32 = 8.0;
+
+/// 0.0から1.0の値を、ドラッグして変更するスライダー
+/// `slider`関数で生成した、つまみの付いたノードで使う
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Slider {
+    pub value: f32,
+}
+
+/// スライダーの値までを塗りつぶすノードの目印
+#[derive(Component, Debug)]
+struct SliderFill;
+
+/// スライダーのつまみの目印
+#[derive(Component, Debug)]
+struct SliderHandle;
+
+/// スライダーの値を更新するシステムセット
+/// 値を読むシステムをこれより後に置くと、同じフレームで変更が反映される
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SliderSet;
+
+/// スライダーをドラッグで操作し、値に合わせて表示を更新するプラグイン
+pub struct SliderPlugin;
+
+impl Plugin for SliderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            drag_slider,
+            update_slider,
+        ).chain().in_set(SliderSet));
+    }
+}
+
+/// スライダーのプラグインを追加する関数
+/// 複数のサンプルから呼ばれても、1回だけ追加する
+pub fn add_slider(app: &mut App) {
+    if !app.is_plugin_added::<SliderPlugin>() {
+        app.add_plugins(SliderPlugin);
+    }
+}
+
+/// 値と大きさを指定して、スライダーのノードを返す関数
+/// 範囲外の値は0.0から1.0に収め、塗りつぶしとつまみもその値に合わせる
+pub fn slider(value: f32, size: Vec2) -> impl Bundle {
+    let value = value.clamp(0.0, 1.0);
+    (
+        Slider { value },
+        Button,
+        RelativeCursorPosition::default(),
+        Node {
+            width: Val::Px(size.x),
+            height: Val::Px(size.y),
+            ..Default::default()
+        },
+        BackgroundColor(TRACK_COLOR),
+        children![
+            (
+                SliderFill,
+                Node {
+                    width: Val::Percent(value * 100.0),
+                    height: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                BackgroundColor(FILL_COLOR),
+            ),
+            (
+                SliderHandle,
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(value * size.x - HANDLE_WIDTH / 2.0),
+                    width: Val::Px(HANDLE_WIDTH),
+                    height: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                BackgroundColor(HANDLE_COLOR),
+            ),
+        ],
+    )
+}
+
+/// 押している間、カーソルの位置にスライダーの値を合わせる関数
+/// ボタンを押し続けている間はノードの外にカーソルが出ても操作できる
+fn drag_slider(
+    mut query: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>,
+) {
+    info_once!("drag_slider");
+
+    for (interaction, cursor, mut slider) in &mut query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else {
+            continue;
+        };
+        let value = position.x.clamp(0.0, 1.0);
+        if slider.value != value {
+            slider.value = value;
+        }
+    }
+}
+
+/// スライダーの塗りつぶしのノードを絞り込むフィルター
+type FillFilter = (With<SliderFill>, Without<Slider>, Without<SliderHandle>);
+/// スライダーのつまみのノードを絞り込むフィルター
+type HandleFilter = (With<SliderHandle>, Without<Slider>, Without<SliderFill>);
+
+/// スライダーの値が変わったら、塗りつぶしとつまみの位置を更新する関数
+fn update_slider(
+    slider_query: Query<(&Slider, &Node, &Children), Changed<Slider>>,
+    mut fill_query: Query<&mut Node, FillFilter>,
+    mut handle_query: Query<&mut Node, HandleFilter>,
+) {
+    info_once!("update_slider");
+
+    for (slider, node, children) in &slider_query {
+        let width = match node.width {
+            Val::Px(width) => width,
+            _ => 0.0,
+        };
+        for child in children {
+            if let Ok(mut fill) = fill_query.get_mut(*child) {
+                fill.width = Val::Percent(slider.value * 100.0);
+            }
+            if let Ok(mut handle) = handle_query.get_mut(*child) {
+                handle.left = Val::Px(slider.value * width - HANDLE_WIDTH / 2.0);
+            }
+        }
+    }
+}
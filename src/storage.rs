@@ -0,0 +1,71 @@
+use std::{env, path::PathBuf};
+
+use bevy::prelude::*;
+
+/// データを保存するフォルダの名前
+pub const APP_DIR: &str = "example-bevy";
+/// 保存先を差し替えたときの、データフォルダの名前
+pub const DATA_DIR: &str = "data";
+/// 保存先を差し替えたときの、設定フォルダの名前
+pub const CONFIG_DIR: &str = "config";
+
+/// データと設定の保存先を差し替えるリソース
+/// 追加すると、プラットフォームのフォルダの代わりに、このフォルダの`data`と`config`に保存する
+/// テストで本当のフォルダを読み書きしないために使う
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct StorageRoot(pub PathBuf);
+
+/// 保存先のリソースがあればその中の、無ければプラットフォームのデータフォルダを返す関数
+pub fn world_data_dir(world: &World) -> Option<PathBuf> {
+    match world.get_resource::<StorageRoot>() {
+        Some(root) => Some(root.0.join(DATA_DIR)),
+        None => data_dir(),
+    }
+}
+
+/// 保存先のリソースがあればその中の、無ければプラットフォームの設定フォルダを返す関数
+pub fn world_config_dir(world: &World) -> Option<PathBuf> {
+    match world.get_resource::<StorageRoot>() {
+        Some(root) => Some(root.0.join(CONFIG_DIR)),
+        None => config_dir(),
+    }
+}
+
+/// プラットフォームごとのデータフォルダを返す関数
+/// - Windows: `%APPDATA%\example-bevy`
+/// - macOS: `~/Library/Application Support/example-bevy`
+/// - その他: `$XDG_DATA_HOME/example-bevy`か`~/.local/share/example-bevy`
+///
+/// 場所が分からない場合は`None`を返す
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env_path("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        env_path("HOME")?.join("Library").join("Application Support")
+    } else {
+        env_path("XDG_DATA_HOME").or_else(|| Some(env_path("HOME")?.join(".local").join("share")))?
+    };
+    Some(base.join(APP_DIR))
+}
+
+/// プラットフォームごとの設定フォルダを返す関数
+/// - Windows: `%APPDATA%\example-bevy`
+/// - macOS: `~/Library/Application Support/example-bevy`
+/// - その他: `$XDG_CONFIG_HOME/example-bevy`か`~/.config/example-bevy`
+///
+/// 場所が分からない場合は`None`を返す
+pub fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env_path("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        env_path("HOME")?.join("Library").join("Application Support")
+    } else {
+        env_path("XDG_CONFIG_HOME").or_else(|| Some(env_path("HOME")?.join(".config")))?
+    };
+    Some(base.join(APP_DIR))
+}
+
+/// 環境変数が空でなければパスとして返す関数
+fn env_path(key: &str) -> Option<PathBuf> {
+    env::var_os(key).filter(|value| !value.is_empty()).map(PathBuf::from)
+}
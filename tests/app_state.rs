@@ -0,0 +1,118 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::{
+    audio::{AudioGain, MusicPlayer, MusicTrack},
+    demos::{
+        Demo,
+        app_state::*,
+    },
+};
+
+/// 現在のサンプル内のステートを返す関数
+fn state(app: &App) -> AppState {
+    app.world().resource::<State<AppState>>().get().clone()
+}
+
+#[test]
+fn starts_in_mainmenu() {
+    let mut app = common::demo_app(Demo::AppState, AppStateDemoPlugin);
+    assert_eq!(state(&app), AppState::Mainmenu);
+    assert_eq!(common::count::<With<Mainmenu>>(&mut app), 1);
+}
+
+#[test]
+fn ingame_to_pause_and_back() {
+    let mut app = common::demo_app(Demo::AppState, AppStateDemoPlugin);
+    common::press_key(&mut app, KEY_MAINMENU_TO_INGAME);
+    assert_eq!(state(&app), AppState::Ingame);
+    assert_eq!(common::count::<With<Mainmenu>>(&mut app), 0);
+    assert_eq!(common::count::<With<Ingame>>(&mut app), 1);
+
+    common::press_key(&mut app, KEY_INGAME_TO_PAUSE);
+    assert_eq!(state(&app), AppState::Pause);
+    assert_eq!(common::count::<With<Ingame>>(&mut app), 0);
+    assert_eq!(common::count::<With<Pause>>(&mut app), 1);
+
+    common::press_key(&mut app, KEY_PAUSE_TO_INGAME);
+    assert_eq!(state(&app), AppState::Ingame);
+    assert_eq!(common::count::<With<Pause>>(&mut app), 0);
+}
+
+#[test]
+fn gameover_transitions() {
+    let mut app = common::demo_app(Demo::AppState, AppStateDemoPlugin);
+    common::press_key(&mut app, KEY_MAINMENU_TO_INGAME);
+    common::press_key(&mut app, KEY_INGAME_TO_GAMEOVER);
+    assert_eq!(state(&app), AppState::Gameover);
+    assert_eq!(common::count::<With<Gameover>>(&mut app), 1);
+
+    common::press_key(&mut app, KEY_GAMEOVER_TO_INGAME);
+    assert_eq!(state(&app), AppState::Ingame);
+
+    common::press_key(&mut app, KEY_INGAME_TO_GAMEOVER);
+    common::press_key(&mut app, KEY_GAMEOVER_TO_MAINMENU);
+    assert_eq!(state(&app), AppState::Mainmenu);
+    assert_eq!(common::count::<With<Gameover>>(&mut app), 0);
+}
+
+#[test]
+fn leaving_demo_removes_sub_state() {
+    let mut app = common::demo_app(Demo::AppState, AppStateDemoPlugin);
+    common::press_key(&mut app, KEY_MAINMENU_TO_INGAME);
+    app.world_mut().resource_mut::<NextState<Demo>>().set(Demo::Menu);
+    app.update();
+    assert!(app.world().get_resource::<State<AppState>>().is_none());
+    assert_eq!(common::count::<With<Ingame>>(&mut app), 0);
+    assert_eq!(common::count::<With<Camera2d>>(&mut app), 0);
+}
+
+/// 再生中の曲を返す関数
+fn current_music(app: &App) -> Option<Handle<AudioSource>> {
+    app.world().resource::<MusicPlayer>().current_track().cloned()
+}
+
+/// 曲の、再生中かとフェードの音量の一覧を返す関数
+fn music_tracks(app: &mut App) -> Vec<(bool, f32, Handle<AudioSource>)> {
+    let mut query = app.world_mut().query::<(&MusicTrack, &AudioGain, &AudioPlayer)>();
+    query.iter(app.world()).map(|(track, gain, player)| (track.current, gain.0, player.0.clone())).collect()
+}
+
+#[test]
+fn state_music_follows_transitions() {
+    let mut app = common::demo_app(Demo::AppState, AppStateDemoPlugin);
+    common::advance(&mut app, 1);
+    let music = app.world().resource::<StateMusic>().clone();
+    assert_eq!(current_music(&app), Some(music.mainmenu.clone()));
+    assert_eq!(music.track(&AppState::Ingame), Some(StateTrack::Playlist));
+
+    // ゲームはプレイリストの曲を流し、メインメニューの曲からクロスフェードする
+    common::press_key(&mut app, KEY_MAINMENU_TO_INGAME);
+    let ingame = app.world().resource::<MusicPlayer>().playlist()[0].clone();
+    assert_eq!(app.world().resource::<MusicPlayer>().current_index(), Some(0));
+    assert_eq!(current_music(&app), Some(ingame.clone()));
+    let tracks = music_tracks(&mut app);
+    assert_eq!(tracks.len(), 2);
+    assert!(tracks.iter().any(|(current, gain, _)| !current && *gain < 1.0));
+    assert!(tracks.iter().any(|(current, gain, _)| *current && *gain < 1.0));
+
+    // ポーズ中はゲームの曲を流し続け、ゲームに戻っても最初から流し直さない
+    common::press_key(&mut app, KEY_INGAME_TO_PAUSE);
+    assert_eq!(current_music(&app), Some(ingame.clone()));
+    common::press_key(&mut app, KEY_PAUSE_TO_INGAME);
+    assert_eq!(music_tracks(&mut app).iter().filter(|(current, _, _)| *current).count(), 1);
+
+    // ゲームオーバーのジングルはフェードインせずに流れ、ゲームの曲はフェードアウトする
+    common::press_key(&mut app, KEY_INGAME_TO_GAMEOVER);
+    assert_eq!(current_music(&app), Some(music.gameover.clone()));
+    let tracks = music_tracks(&mut app);
+    assert!(tracks.contains(&(true, 1.0, music.gameover.clone())));
+    assert!(tracks.iter().any(|(current, gain, _)| !current && *gain < 1.0));
+
+    // サンプルを離れると曲を止める
+    app.world_mut().resource_mut::<NextState<Demo>>().set(Demo::Menu);
+    common::advance(&mut app, 2);
+    assert_eq!(current_music(&app), None);
+    assert!(!app.world().contains_resource::<StateMusic>());
+}
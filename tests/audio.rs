@@ -0,0 +1,284 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use bevy::{
+    prelude::*,
+    audio::Volume,
+    ui::RelativeCursorPosition,
+};
+
+use example_bevy::{
+    audio::{AudioBus, AudioBusPlugin, AudioBuses, AudioGain, AudioSettings, MusicPlayer, MusicTrack, settings::AUDIO_SETTINGS_FILE},
+    demos::{Demo, audio::{AudioDemoPlugin, BusAction, BusButton, KEY_NEXT_TRACK, VolumeSlider}},
+    slider::{Slider, slider},
+    storage::{CONFIG_DIR, StorageRoot},
+};
+
+/// 設定ファイルの保存先を指定した、オーディオのサンプルのプラグイン
+fn audio_plugins(settings_path: PathBuf) -> (AudioBusPlugin, AudioDemoPlugin) {
+    (AudioBusPlugin { settings_path: Some(settings_path) }, AudioDemoPlugin)
+}
+
+#[test]
+fn left_click_plays_click_sound() {
+    let mut app = common::demo_app(Demo::Audio, AudioDemoPlugin);
+    // BGMのみ
+    assert_eq!(common::count::<With<AudioPlayer>>(&mut app), 1);
+
+    common::click_mouse(&mut app, MouseButton::Left);
+    assert_eq!(common::count::<With<AudioPlayer>>(&mut app), 2);
+
+    common::click_mouse(&mut app, MouseButton::Right);
+    assert_eq!(common::count::<With<AudioPlayer>>(&mut app), 2);
+}
+
+#[test]
+fn click_sound_uses_sfx_bus() {
+    let mut app = common::demo_app(Demo::Audio, AudioDemoPlugin);
+    {
+        let mut buses = app.world_mut().resource_mut::<AudioBuses>();
+        buses.master.volume = 0.5;
+        buses.sfx.volume = 0.5;
+        buses.bgm.muted = true;
+    }
+    common::click_mouse(&mut app, MouseButton::Left);
+
+    let mut query = app.world_mut().query::<(&PlaybackSettings, &AudioBus)>();
+    let (settings, _) = query.iter(app.world())
+        .find(|(_, bus)| **bus == AudioBus::Sfx)
+        .unwrap();
+    assert_eq!(settings.volume, Volume::Linear(0.25));
+    assert!(!settings.muted);
+}
+
+#[test]
+fn bgm_starts_with_bus_settings() {
+    let mut app = App::new();
+    app
+        .add_plugins(common::base_plugin(u32::MAX))
+        .insert_state(Demo::Menu)
+        .add_plugins(AudioDemoPlugin);
+    app.finish();
+    app.cleanup();
+    {
+        let mut buses = app.world_mut().resource_mut::<AudioBuses>();
+        buses.bgm.volume = 0.3;
+        buses.master.muted = true;
+    }
+    app.world_mut().resource_mut::<NextState<Demo>>().set(Demo::Audio);
+    common::advance(&mut app, 2);
+
+    let mut query = app.world_mut().query_filtered::<(&PlaybackSettings, &AudioBus), With<MusicTrack>>();
+    let (settings, bus) = query.single(app.world()).unwrap();
+    assert_eq!(*bus, AudioBus::Bgm);
+    assert_eq!(settings.volume, Volume::Linear(0.3));
+    assert!(settings.muted);
+    assert!(!settings.paused);
+
+    // スライダーも系統の音量から始まる
+    let mut query = app.world_mut().query::<(&Slider, &VolumeSlider)>();
+    let bgm_slider = query.iter(app.world()).find(|(_, slider)| slider.0 == AudioBus::Bgm).unwrap();
+    assert_eq!(bgm_slider.0.value, 0.3);
+}
+
+#[test]
+fn out_of_range_slider_is_drawn_at_clamped_value() {
+    let mut world = World::new();
+    let slider = world.spawn(slider(1.5, Vec2::new(200.0, 20.0))).id();
+    assert_eq!(world.get::<Slider>(slider).unwrap().value, 1.0);
+
+    // 塗りつぶしは端まで伸び、つまみは右端に置かれる
+    let children: Vec<Entity> = world.get::<Children>(slider).unwrap().to_vec();
+    let nodes: Vec<&Node> = children.iter().map(|&child| world.get::<Node>(child).unwrap()).collect();
+    assert_eq!(nodes[0].width, Val::Percent(100.0));
+    let Val::Px(handle_width) = nodes[1].width else {
+        panic!("handle width should be in pixels");
+    };
+    assert_eq!(nodes[1].left, Val::Px(200.0 - handle_width / 2.0));
+}
+
+#[test]
+fn dragging_slider_changes_bus_volume() {
+    let mut app = common::demo_app(Demo::Audio, AudioDemoPlugin);
+    let mut query = app.world_mut().query::<(Entity, &VolumeSlider)>();
+    let (slider, _) = query.iter(app.world()).find(|(_, slider)| slider.0 == AudioBus::Bgm).unwrap();
+
+    // ノードの外までドラッグしても、値は0.0から1.0に収まる
+    for (x, expected) in [(0.25, 0.25), (1.5, 1.0)] {
+        app.world_mut().entity_mut(slider).insert((
+            Interaction::Pressed,
+            RelativeCursorPosition {
+                normalized: Some(Vec2::new(x, 0.5)),
+                ..Default::default()
+            },
+        ));
+        app.update();
+        assert_eq!(app.world().resource::<AudioBuses>().bgm.volume, expected);
+        assert_eq!(app.world().get::<Slider>(slider).unwrap().value, expected);
+    }
+
+    // 押していなければ変わらない
+    app.world_mut().entity_mut(slider).insert((
+        Interaction::None,
+        RelativeCursorPosition {
+            normalized: Some(Vec2::new(0.1, 0.5)),
+            ..Default::default()
+        },
+    ));
+    app.update();
+    assert_eq!(app.world().resource::<AudioBuses>().bgm.volume, 1.0);
+}
+
+#[test]
+fn buttons_toggle_mute_and_pause() {
+    let mut app = common::demo_app(Demo::Audio, AudioDemoPlugin);
+    let mut query = app.world_mut().query::<(Entity, &BusButton)>();
+    let buttons: Vec<(Entity, BusButton)> = query.iter(app.world()).map(|(entity, button)| (entity, *button)).collect();
+    assert_eq!(buttons.len(), AudioBus::ALL.len() * 2);
+    let find = |bus, action| buttons.iter().find(|(_, button)| *button == BusButton { bus, action }).unwrap().0;
+
+    let press = |app: &mut App, entity: Entity| {
+        app.world_mut().entity_mut(entity).insert(Interaction::Pressed);
+        app.update();
+        app.world_mut().entity_mut(entity).insert(Interaction::None);
+        app.update();
+    };
+    press(&mut app, find(AudioBus::Master, BusAction::Mute));
+    press(&mut app, find(AudioBus::Sfx, BusAction::Pause));
+    let buses = app.world().resource::<AudioBuses>().clone();
+    assert!(buses.master.muted && buses.is_muted(AudioBus::Bgm));
+    assert!(buses.sfx.paused && !buses.is_paused(AudioBus::Bgm));
+
+    press(&mut app, find(AudioBus::Master, BusAction::Mute));
+    assert!(!app.world().resource::<AudioBuses>().is_muted(AudioBus::Bgm));
+}
+
+#[test]
+fn settings_round_trip_without_pause() {
+    let path = common::temp_path("audio_round_trip.ron");
+    let _ = fs::remove_file(&path);
+
+    // ファイルが無ければ既定の設定
+    let settings = AudioSettings::load(&path).unwrap();
+    assert_eq!(*settings.buses(), AudioBuses::default());
+    assert_eq!(settings.path(), Some(path.as_path()));
+
+    let mut buses = AudioBuses::default();
+    buses.bgm.volume = 0.4;
+    buses.sfx.muted = true;
+    buses.master.paused = true;
+    AudioSettings::new(buses.clone(), Some(path.clone())).save().unwrap();
+
+    // 一時停止は保存されない
+    let loaded = AudioSettings::load(&path).unwrap();
+    buses.master.paused = false;
+    assert_eq!(*loaded.buses(), buses);
+
+    fs::write(&path, "not ron").unwrap();
+    assert!(AudioSettings::load(&path).is_err());
+    assert_eq!(*AudioSettings::load_or_default(Some(path.clone())).buses(), AudioBuses::default());
+}
+
+#[test]
+fn changing_buses_saves_settings() {
+    let path = common::temp_path("audio_changes.ron");
+    let _ = fs::remove_file(&path);
+    let mut app = common::demo_app(Demo::Audio, audio_plugins(path.clone()));
+    assert!(!path.exists());
+
+    // 一時停止だけでは保存しない
+    app.world_mut().resource_mut::<AudioBuses>().bgm.paused = true;
+    app.update();
+    assert!(!path.exists());
+
+    {
+        let mut buses = app.world_mut().resource_mut::<AudioBuses>();
+        buses.bgm.volume = 0.6;
+        buses.master.muted = true;
+    }
+    app.update();
+    let saved = AudioSettings::load(&path).unwrap();
+    assert_eq!(saved.buses().bgm.volume, 0.6);
+    assert!(saved.buses().master.muted && !saved.buses().bgm.paused);
+}
+
+#[test]
+fn bgm_starts_with_saved_settings() {
+    let path = common::temp_path("audio_saved.ron");
+    let mut buses = AudioBuses::default();
+    buses.bgm.volume = 0.2;
+    buses.bgm.muted = true;
+    AudioSettings::new(buses, Some(path.clone())).save().unwrap();
+
+    let mut app = common::demo_app(Demo::Audio, audio_plugins(path));
+    assert_eq!(app.world().resource::<AudioBuses>().bgm.volume, 0.2);
+    let mut query = app.world_mut().query_filtered::<&PlaybackSettings, With<MusicTrack>>();
+    let settings = query.single(app.world()).unwrap();
+    assert_eq!(settings.volume, Volume::Linear(0.2));
+    assert!(settings.muted);
+}
+
+/// 曲の、再生中かとフェードの音量の一覧を返す関数
+fn music_tracks(app: &mut App) -> Vec<(bool, f32)> {
+    let mut query = app.world_mut().query::<(&MusicTrack, &AudioGain)>();
+    query.iter(app.world()).map(|(track, gain)| (track.current, gain.0)).collect()
+}
+
+#[test]
+fn next_track_crossfades_and_leaving_fades_out() {
+    let mut app = common::demo_app(Demo::Audio, AudioDemoPlugin);
+    assert_eq!(music_tracks(&mut app), vec![(true, 1.0)]);
+    assert_eq!(app.world().resource::<MusicPlayer>().current_index(), Some(0));
+
+    // 次の曲はフェードインし、前の曲はフェードアウトする
+    common::press_key(&mut app, KEY_NEXT_TRACK);
+    let mut tracks = music_tracks(&mut app);
+    tracks.sort_by_key(|(current, _)| *current);
+    assert_eq!(tracks.len(), 2);
+    assert!(!tracks[0].0 && tracks[0].1 < 1.0);
+    assert!(tracks[1].0 && tracks[1].1 > 0.0 && tracks[1].1 < 1.0);
+
+    let crossfade = app.world().resource::<MusicPlayer>().crossfade;
+    common::advance(&mut app, (crossfade * 60.0) as u32);
+    assert_eq!(music_tracks(&mut app), vec![(true, 1.0)]);
+
+    // サンプルを離れると、フェードアウトしてから削除される
+    app.world_mut().resource_mut::<NextState<Demo>>().set(Demo::Menu);
+    common::advance(&mut app, 2);
+    assert!(!app.world().resource::<MusicPlayer>().is_playing());
+    assert_eq!(music_tracks(&mut app).len(), 1);
+    common::advance(&mut app, (crossfade * 60.0) as u32);
+    assert!(music_tracks(&mut app).is_empty());
+}
+
+#[test]
+fn finished_track_is_followed_without_crossfade() {
+    let mut app = common::demo_app(Demo::Audio, AudioDemoPlugin);
+    common::advance(&mut app, 1);
+
+    // 曲が最後まで再生されたときと同じように、曲を削除して次の曲に移る
+    let mut query = app.world_mut().query_filtered::<Entity, With<MusicTrack>>();
+    let finished = query.single(app.world()).unwrap();
+    app.world_mut().despawn(finished);
+    app.world_mut().resource_mut::<MusicPlayer>().finish_track();
+    app.update();
+
+    // 次の曲はフェードインせずに、すぐに聞こえる
+    assert_eq!(music_tracks(&mut app), vec![(true, 1.0)]);
+    assert_eq!(app.world().resource::<MusicPlayer>().current_index(), Some(0));
+    let mut query = app.world_mut().query_filtered::<&PlaybackSettings, With<MusicTrack>>();
+    assert_eq!(query.single(app.world()).unwrap().volume, Volume::Linear(1.0));
+}
+
+#[test]
+fn settings_are_saved_under_storage_root() {
+    let mut app = common::demo_app(Demo::Audio, AudioDemoPlugin);
+    let root = app.world().resource::<StorageRoot>().0.clone();
+    let path = app.world().resource::<AudioSettings>().path().unwrap().to_path_buf();
+    assert_eq!(path, root.join(CONFIG_DIR).join(AUDIO_SETTINGS_FILE));
+
+    app.world_mut().resource_mut::<AudioBuses>().sfx.volume = 0.5;
+    app.update();
+    assert_eq!(AudioSettings::load(&path).unwrap().buses().sfx.volume, 0.5);
+}
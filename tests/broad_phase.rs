@@ -0,0 +1,68 @@
+use bevy::{
+    prelude::*,
+    math::bounding::{Aabb2d, IntersectsVolume},
+};
+
+use example_bevy::physics::{BroadPhase, CollisionLayers};
+
+/// 境界ボックスを登録した空間ハッシュを作成する関数
+fn broad_phase(aabbs: &[Aabb2d]) -> BroadPhase {
+    let mut broad_phase = BroadPhase::new(10.0);
+    for (i, aabb) in aabbs.iter().enumerate() {
+        broad_phase.insert(Entity::from_raw(i as u32), *aabb, CollisionLayers::default());
+    }
+    broad_phase.update_pairs();
+    broad_phase
+}
+
+#[test]
+fn pairs_match_all_pairs() {
+    // セルの境界をまたぐ大きさと位置の境界ボックスを並べる
+    let aabbs: Vec<Aabb2d> = (0..100)
+        .map(|i| {
+            let center = Vec2::new((i * 37 % 100) as f32, (i * 61 % 100) as f32);
+            Aabb2d::new(center, Vec2::splat(2.0 + (i % 7) as f32 * 3.0))
+        })
+        .collect();
+    let broad_phase = broad_phase(&aabbs);
+
+    let mut expected = Vec::new();
+    for i in 0..aabbs.len() {
+        for j in i + 1..aabbs.len() {
+            if aabbs[i].intersects(&aabbs[j]) {
+                expected.push((Entity::from_raw(i as u32), Entity::from_raw(j as u32)));
+            }
+        }
+    }
+    assert!(!expected.is_empty());
+    assert_eq!(broad_phase.pairs(), expected);
+}
+
+#[test]
+fn large_aabbs_are_paired_once() {
+    // 多くのセルにまたがる境界ボックス同士でも、組は1つだけになる
+    let broad_phase = broad_phase(&[
+        Aabb2d::new(Vec2::ZERO, Vec2::splat(50.0)),
+        Aabb2d::new(Vec2::splat(5.0), Vec2::splat(40.0)),
+        Aabb2d::new(Vec2::splat(200.0), Vec2::splat(1.0)),
+    ]);
+    assert_eq!(broad_phase.pairs(), [(Entity::from_raw(0), Entity::from_raw(1))]);
+}
+
+#[test]
+fn pairs_skip_layers_that_do_not_interact() {
+    let player = CollisionLayers::new(0b001, 0b110);
+    let bullet = CollisionLayers::new(0b010, 0b100);
+    let wall = CollisionLayers::default();
+    let mut broad_phase = BroadPhase::new(10.0);
+    for (i, layers) in [player, bullet, wall].into_iter().enumerate() {
+        broad_phase.insert(Entity::from_raw(i as u32), Aabb2d::new(Vec2::ZERO, Vec2::ONE), layers);
+    }
+    broad_phase.update_pairs();
+
+    // 弾はプレイヤーと衝突せず、壁は全てと衝突する
+    assert_eq!(broad_phase.pairs(), [
+        (Entity::from_raw(0), Entity::from_raw(2)),
+        (Entity::from_raw(1), Entity::from_raw(2)),
+    ]);
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::physics::{Ccd, Collider, Position, Velocity, ccd::time_of_impact};
+
+/// 1ティックで100進む速さ
+const FAST_SPEED: f32 = 6000.0;
+
+/// 薄い壁に向かって速いボールを飛ばすアプリを作成する関数
+fn fast_ball_and_thin_wall(ccd: bool) -> (App, Entity) {
+    let mut app = common::physics_app();
+    app.world_mut().spawn((
+        Transform::default(),
+        Collider::rectangle(2.0, 100.0),
+    ));
+    let mut ball = app.world_mut().spawn((
+        Transform::from_xyz(-60.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(FAST_SPEED, 0.0)),
+    ));
+    if ccd {
+        ball.insert(Ccd);
+    }
+    let ball = ball.id();
+    app.update();
+    (app, ball)
+}
+
+#[test]
+fn fast_ball_tunnels_without_ccd() {
+    let (mut app, ball) = fast_ball_and_thin_wall(false);
+
+    common::advance(&mut app, 2);
+    assert!(app.world().get::<Position>(ball).unwrap().x > 0.0);
+    assert_eq!(app.world().get::<Velocity>(ball).unwrap().x, FAST_SPEED);
+}
+
+#[test]
+fn fast_ball_bounces_with_ccd() {
+    let (mut app, ball) = fast_ball_and_thin_wall(true);
+
+    common::advance(&mut app, 2);
+    assert!(app.world().get::<Position>(ball).unwrap().x < 0.0);
+    assert_eq!(app.world().get::<Velocity>(ball).unwrap().x, -FAST_SPEED);
+}
+
+#[test]
+fn fast_balls_do_not_pass_through_each_other() {
+    let mut app = common::physics_app();
+    let left = app.world_mut().spawn((
+        Transform::from_xyz(-45.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(FAST_SPEED, 0.0)),
+        Ccd,
+    )).id();
+    let right = app.world_mut().spawn((
+        Transform::from_xyz(45.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(-FAST_SPEED, 0.0)),
+        Ccd,
+    )).id();
+    app.update();
+
+    common::advance(&mut app, 1);
+    assert!(app.world().get::<Position>(left).unwrap().x < app.world().get::<Position>(right).unwrap().x);
+    assert!(app.world().get::<Velocity>(left).unwrap().x < 0.0);
+    assert!(app.world().get::<Velocity>(right).unwrap().x > 0.0);
+}
+
+#[test]
+fn fast_ball_bounces_off_moving_ball() {
+    // CCDの無い相手も当たった時刻まで戻さないと、離れたままになり反射しない
+    let mut app = common::physics_app();
+    let ball = app.world_mut().spawn((
+        Transform::from_xyz(-70.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(FAST_SPEED, 0.0)),
+        Ccd,
+    )).id();
+    let other = app.world_mut().spawn((
+        Transform::from_xyz(50.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(-FAST_SPEED / 2.0, 0.0)),
+    )).id();
+    app.update();
+
+    common::advance(&mut app, 3);
+    let position = |entity| app.world().get::<Position>(entity).unwrap().x;
+    assert!(position(ball) < position(other));
+    assert!(app.world().get::<Velocity>(ball).unwrap().x < 0.0);
+    assert!(app.world().get::<Velocity>(other).unwrap().x > 0.0);
+}
+
+#[test]
+fn fast_balls_with_different_speeds_collide_head_on() {
+    let mut app = common::physics_app();
+    let left = app.world_mut().spawn((
+        Transform::from_xyz(-80.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(FAST_SPEED * 1.5, 0.0)),
+        Ccd,
+    )).id();
+    let right = app.world_mut().spawn((
+        Transform::from_xyz(40.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(-FAST_SPEED / 2.0, 0.0)),
+        Ccd,
+    )).id();
+    app.update();
+
+    common::advance(&mut app, 3);
+    let position = |entity| app.world().get::<Position>(entity).unwrap().x;
+    assert!(position(left) < position(right));
+    assert!(app.world().get::<Velocity>(left).unwrap().x < 0.0);
+    assert!(app.world().get::<Velocity>(right).unwrap().x > 0.0);
+}
+
+#[test]
+fn time_of_impact_of_swept_shapes() {
+    let circle = Collider::circle(5.0);
+    let square = Collider::rectangle(10.0, 10.0);
+    let at = Isometry2d::from_xy;
+
+    // 円同士は中心の距離が半径の和になったときに触れる
+    let time = time_of_impact(&circle, at(0.0, 0.0), Vec2::new(100.0, 0.0), &circle, at(50.0, 0.0), Vec2::ZERO);
+    assert!((time.unwrap() - 0.4).abs() < 1e-4, "{time:?}");
+
+    // 両方が動く場合は相対的な移動で判定する
+    let time = time_of_impact(&circle, at(0.0, 0.0), Vec2::new(50.0, 0.0), &circle, at(50.0, 0.0), Vec2::new(-50.0, 0.0));
+    assert!((time.unwrap() - 0.4).abs() < 1e-4, "{time:?}");
+
+    // 境界ボックス同士
+    let time = time_of_impact(&square, at(0.0, 0.0), Vec2::new(0.0, 100.0), &square, at(0.0, 50.0), Vec2::ZERO);
+    assert!((time.unwrap() - 0.4).abs() < 1e-4, "{time:?}");
+
+    // 境界ボックスの角をかすめる円は、角の丸みで判定する
+    let time = time_of_impact(&circle, at(-50.0, 9.0), Vec2::new(100.0, 0.0), &square, at(0.0, 0.0), Vec2::ZERO);
+    assert!((time.unwrap() - 0.42).abs() < 1e-4, "{time:?}");
+    assert_eq!(time_of_impact(&circle, at(-50.0, 14.0), Vec2::new(100.0, 0.0), &square, at(0.0, 0.0), Vec2::ZERO), None);
+
+    // 最初から重なっている場合と、届かない場合は判定しない
+    assert_eq!(time_of_impact(&circle, at(0.0, 0.0), Vec2::X, &circle, at(5.0, 0.0), Vec2::ZERO), None);
+    assert_eq!(time_of_impact(&circle, at(0.0, 0.0), Vec2::new(10.0, 0.0), &circle, at(50.0, 0.0), Vec2::ZERO), None);
+}
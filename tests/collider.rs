@@ -0,0 +1,172 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use bevy::{math::bounding::BoundingVolume, prelude::*};
+
+use example_bevy::physics::Collider;
+
+/// 形状の位置と回転
+fn at(x: f32, y: f32, angle: f32) -> Isometry2d {
+    Isometry2d::new(Vec2::new(x, y), Rot2::radians(angle))
+}
+
+/// 接触を判定し、法線とめり込みを確認する関数
+fn assert_contact(a: Collider, iso_a: Isometry2d, b: Collider, iso_b: Isometry2d, normal: Vec2, penetration: f32) {
+    let contact = a.contact(iso_a, &b, iso_b)
+        .unwrap_or_else(|| panic!("{a:?} and {b:?} should touch"));
+    assert!(contact.normal.distance(normal) < 1e-3, "normal {}", contact.normal);
+    assert!((contact.penetration - penetration).abs() < 1e-3, "penetration {}", contact.penetration);
+
+    // 順番を入れ替えると法線が反転する
+    let reversed = b.contact(iso_b, &a, iso_a).unwrap();
+    assert!(reversed.normal.distance(-normal) < 1e-3, "reversed normal {}", reversed.normal);
+    assert!((reversed.penetration - penetration).abs() < 1e-3);
+}
+
+/// 接触していないことを確認する関数
+fn assert_separated(a: Collider, iso_a: Isometry2d, b: Collider, iso_b: Isometry2d) {
+    assert_eq!(a.contact(iso_a, &b, iso_b), None, "{a:?} and {b:?} should not touch");
+    assert_eq!(b.contact(iso_b, &a, iso_a), None);
+}
+
+#[test]
+fn circle_circle() {
+    let circle = Collider::circle(10.0);
+    assert_contact(circle, at(0.0, 0.0, 0.0), circle, at(15.0, 0.0, 0.0), Vec2::X, 5.0);
+    assert_separated(circle, at(0.0, 0.0, 0.0), circle, at(21.0, 0.0, 0.0));
+}
+
+#[test]
+fn circle_aabb() {
+    let circle = Collider::circle(10.0);
+    let aabb = Collider::rectangle(20.0, 20.0);
+    assert_contact(circle, at(0.0, 18.0, 0.0), aabb, at(0.0, 0.0, 0.0), Vec2::NEG_Y, 2.0);
+    // 中心が矩形の内側にある場合も、近い面から押し出す
+    assert_contact(circle, at(8.0, 0.0, 0.0), aabb, at(0.0, 0.0, 0.0), Vec2::NEG_X, 12.0);
+    // 角の近くでは、角との距離で判定する
+    assert_separated(circle, at(18.0, 18.0, 0.0), aabb, at(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn circle_obb() {
+    let circle = Collider::circle(10.0);
+    let obb = Collider::oriented_rectangle(20.0, 20.0);
+    // 45度回転した矩形の角は中心から約14.14離れている
+    let corner = 10.0 * 2.0_f32.sqrt();
+    assert_contact(circle, at(corner + 8.0, 0.0, 0.0), obb, at(0.0, 0.0, FRAC_PI_4), Vec2::NEG_X, 2.0);
+    assert_separated(circle, at(corner + 11.0, 0.0, 0.0), obb, at(0.0, 0.0, FRAC_PI_4));
+}
+
+#[test]
+fn circle_capsule() {
+    let circle = Collider::circle(10.0);
+    let capsule = Collider::capsule(5.0, 40.0);
+    // カプセルの側面
+    assert_contact(circle, at(12.0, 15.0, 0.0), capsule, at(0.0, 0.0, 0.0), Vec2::NEG_X, 3.0);
+    // カプセルの端
+    assert_contact(circle, at(0.0, 33.0, 0.0), capsule, at(0.0, 0.0, 0.0), Vec2::NEG_Y, 2.0);
+    assert_separated(circle, at(0.0, 36.0, 0.0), capsule, at(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn aabb_aabb() {
+    let aabb = Collider::rectangle(20.0, 20.0);
+    assert_contact(aabb, at(0.0, 0.0, 0.0), aabb, at(16.0, 4.0, 0.0), Vec2::X, 4.0);
+    assert_separated(aabb, at(0.0, 0.0, 0.0), aabb, at(21.0, 0.0, 0.0));
+}
+
+#[test]
+fn aabb_ignores_rotation() {
+    let aabb = Collider::rectangle(20.0, 20.0);
+    assert_separated(aabb, at(0.0, 0.0, FRAC_PI_4), aabb, at(21.0, 0.0, FRAC_PI_4));
+    let bounds = aabb.aabb(at(0.0, 0.0, FRAC_PI_4));
+    assert_eq!(bounds.half_size(), Vec2::splat(10.0));
+}
+
+#[test]
+fn aabb_obb() {
+    let aabb = Collider::rectangle(20.0, 20.0);
+    let obb = Collider::oriented_rectangle(20.0, 20.0);
+    let corner = 10.0 * 2.0_f32.sqrt();
+    assert_contact(aabb, at(0.0, 0.0, 0.0), obb, at(10.0 + corner - 1.0, 0.0, FRAC_PI_4), Vec2::X, 1.0);
+    // 回転していなければ重なる距離でも、回転した角が届かなければ離れている
+    assert_separated(aabb, at(0.0, 0.0, 0.0), obb, at(10.0 + corner + 1.0, 0.0, FRAC_PI_4));
+}
+
+#[test]
+fn aabb_capsule() {
+    let aabb = Collider::rectangle(20.0, 20.0);
+    let capsule = Collider::capsule(5.0, 40.0);
+    assert_contact(aabb, at(0.0, 0.0, 0.0), capsule, at(13.0, 0.0, 0.0), Vec2::X, 2.0);
+    // 横倒しのカプセルの端が矩形の上に乗る
+    assert_contact(aabb, at(0.0, 0.0, 0.0), capsule, at(0.0, 14.0, FRAC_PI_2), Vec2::Y, 1.0);
+    assert_separated(aabb, at(0.0, 0.0, 0.0), capsule, at(16.0, 0.0, 0.0));
+}
+
+#[test]
+fn obb_obb() {
+    let obb = Collider::oriented_rectangle(20.0, 20.0);
+    // 斜めの面同士が向かい合う
+    let diagonal = Vec2::ONE.normalize();
+    let overlapping = diagonal * 18.0;
+    assert_contact(obb, at(0.0, 0.0, FRAC_PI_4), obb, at(overlapping.x, overlapping.y, FRAC_PI_4), diagonal, 2.0);
+    let apart = diagonal * 21.0;
+    assert_separated(obb, at(0.0, 0.0, FRAC_PI_4), obb, at(apart.x, apart.y, FRAC_PI_4));
+}
+
+#[test]
+fn obb_capsule() {
+    let obb = Collider::oriented_rectangle(20.0, 20.0);
+    let capsule = Collider::capsule(5.0, 40.0);
+    let corner = 10.0 * 2.0_f32.sqrt();
+    assert_contact(obb, at(0.0, 0.0, FRAC_PI_4), capsule, at(corner + 4.0, 0.0, 0.0), Vec2::X, 1.0);
+    assert_separated(obb, at(0.0, 0.0, FRAC_PI_4), capsule, at(corner + 6.0, 0.0, 0.0));
+}
+
+#[test]
+fn capsule_capsule() {
+    let capsule = Collider::capsule(5.0, 40.0);
+    // 平行に並ぶ
+    assert_contact(capsule, at(0.0, 0.0, 0.0), capsule, at(8.0, 10.0, 0.0), Vec2::X, 2.0);
+    // 十字に交差する芯は、分離軸で押し出す
+    let crossed = capsule.contact(at(0.0, 0.0, 0.0), &capsule, at(0.0, 0.0, FRAC_PI_2)).unwrap();
+    assert!(crossed.penetration > 10.0, "{}", crossed.penetration);
+    assert_separated(capsule, at(0.0, 0.0, 0.0), capsule, at(11.0, 0.0, 0.0));
+}
+
+#[test]
+fn circle_segment() {
+    let circle = Collider::circle(10.0);
+    let segment = Collider::segment(Vec2::new(-50.0, 0.0), Vec2::new(50.0, 0.0));
+    assert_contact(circle, at(0.0, 8.0, 0.0), segment, at(0.0, 0.0, 0.0), Vec2::NEG_Y, 2.0);
+    // 線分の端では、端点との距離で判定する
+    assert_contact(circle, at(58.0, 0.0, 0.0), segment, at(0.0, 0.0, 0.0), Vec2::NEG_X, 2.0);
+    assert_separated(circle, at(58.0, 8.0, 0.0), segment, at(0.0, 0.0, 0.0));
+    // 回転させた線分
+    assert_contact(circle, at(8.0, 0.0, 0.0), segment, at(0.0, 0.0, FRAC_PI_2), Vec2::NEG_X, 2.0);
+}
+
+#[test]
+fn polygon_contacts() {
+    let triangle = Collider::polygon(&[Vec2::new(-20.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(0.0, 20.0)]).unwrap();
+    let circle = Collider::circle(5.0);
+    let square = Collider::rectangle(10.0, 10.0);
+    assert_contact(circle, at(0.0, -3.0, 0.0), triangle, at(0.0, 0.0, 0.0), Vec2::Y, 2.0);
+    assert_contact(square, at(0.0, -4.0, 0.0), triangle, at(0.0, 0.0, 0.0), Vec2::Y, 1.0);
+    assert_separated(circle, at(0.0, 26.0, 0.0), triangle, at(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn polygon_requires_convex_vertices() {
+    // 時計回りの頂点は反時計回りに並べ直す
+    let clockwise = [Vec2::new(0.0, 0.0), Vec2::new(0.0, 10.0), Vec2::new(10.0, 0.0)];
+    let Some(Collider::Polygon(polygon)) = Collider::polygon(&clockwise) else {
+        panic!("triangle should be valid");
+    };
+    assert_eq!(polygon.vertices(), [Vec2::new(10.0, 0.0), Vec2::new(0.0, 10.0), Vec2::new(0.0, 0.0)]);
+
+    // 凹んだ多角形と、頂点が足りない多角形は作れない
+    let concave = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(5.0, 2.0), Vec2::new(10.0, 10.0), Vec2::new(0.0, 10.0)];
+    assert_eq!(Collider::polygon(&concave), None);
+    assert_eq!(Collider::polygon(&[Vec2::ZERO, Vec2::X]), None);
+    assert_eq!(Collider::polygon(&[Vec2::ZERO, Vec2::X, Vec2::new(2.0, 0.0)]), None);
+}
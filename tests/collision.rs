@@ -0,0 +1,132 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::{
+    WINDOW_SIZE,
+    demos::{
+        Demo,
+        collision::{Ball, CollisionDemoPlugin},
+    },
+    physics::{Collider, CollisionLayers, Mass, Position, Velocity},
+};
+
+/// 全てのボールの運動量の合計を返す関数
+fn momentum(app: &mut App) -> Vec2 {
+    let mut query = app.world_mut().query_filtered::<(&Velocity, &Mass), With<Ball>>();
+    query.iter(app.world()).map(|(velocity, mass)| **velocity * **mass).sum()
+}
+
+#[test]
+fn ball_bounces_off_window_edge() {
+    let mut app = common::demo_app(Demo::Collision, CollisionDemoPlugin);
+
+    // 静止しているボールを1つだけ残し、画面端で右向きに動かす
+    let mut query = app.world_mut().query_filtered::<Entity, With<Ball>>();
+    let balls: Vec<Entity> = query.iter(app.world()).collect();
+    for entity in &balls[1..] {
+        app.world_mut().despawn(*entity);
+    }
+    let mut ball = app.world_mut().entity_mut(balls[0]);
+    ball.get_mut::<Position>().unwrap().0 = Vec2::new(WINDOW_SIZE.x / 2.0, 0.0);
+    ball.get_mut::<Velocity>().unwrap().0 = Vec2::new(80.0, 0.0);
+    app.update();
+
+    let velocity = app.world().get::<Velocity>(balls[0]).unwrap();
+    assert_eq!(velocity.0, Vec2::new(-80.0, 0.0));
+}
+
+#[test]
+fn square_bounces_off_ball() {
+    let mut app = common::demo_app(Demo::Collision, CollisionDemoPlugin);
+
+    // ボールを1つだけ残し、その上から四角形を落とす
+    let mut query = app.world_mut().query_filtered::<Entity, With<Ball>>();
+    let balls: Vec<Entity> = query.iter(app.world()).collect();
+    for entity in &balls[1..] {
+        app.world_mut().despawn(*entity);
+    }
+    let mut ball = app.world_mut().entity_mut(balls[0]);
+    ball.get_mut::<Position>().unwrap().0 = Vec2::ZERO;
+    ball.get_mut::<Velocity>().unwrap().0 = Vec2::ZERO;
+    let square = app.world_mut().spawn((
+        Transform::from_xyz(0.0, 29.0, 0.0),
+        Collider::rectangle(20.0, 20.0),
+        Velocity(Vec2::new(0.0, -80.0)),
+    )).id();
+    app.update();
+
+    assert_eq!(app.world().get::<Velocity>(square).unwrap().0, Vec2::ZERO);
+    assert_eq!(app.world().get::<Velocity>(balls[0]).unwrap().0, Vec2::new(0.0, -80.0));
+}
+
+#[test]
+fn newtons_cradle_conserves_momentum() {
+    let mut app = common::demo_app(Demo::Collision, CollisionDemoPlugin);
+    let initial = momentum(&mut app);
+    assert_eq!(initial, Vec2::new(80.0, 0.0));
+
+    // 最後のボールが画面端に届く前まで、ボール同士の衝突だけで運動量が保たれる
+    for _ in 0..120 {
+        app.update();
+        let current = momentum(&mut app);
+        assert!((current - initial).length() < 1e-3, "{current}");
+    }
+
+    // 運動は右端のボールまで伝わり、他のボールは止まる
+    let mut query = app.world_mut().query_filtered::<(&Position, &Velocity), With<Ball>>();
+    let mut balls: Vec<(Vec2, Vec2)> = query.iter(app.world()).map(|(p, v)| (**p, **v)).collect();
+    balls.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+    let (last, others) = balls.split_last().unwrap();
+    assert!((last.1 - Vec2::new(80.0, 0.0)).length() < 1e-3, "{}", last.1);
+    for (_, velocity) in others {
+        assert!(velocity.length() < 1e-3, "{velocity}");
+    }
+}
+
+#[test]
+fn overlapping_balls_are_separated() {
+    let mut app = common::demo_app(Demo::Collision, CollisionDemoPlugin);
+
+    // 止まっている2つのボールを重ねる
+    let mut query = app.world_mut().query_filtered::<Entity, With<Ball>>();
+    let balls: Vec<Entity> = query.iter(app.world()).collect();
+    for entity in &balls[2..] {
+        app.world_mut().despawn(*entity);
+    }
+    for (entity, x) in [(balls[0], -5.0), (balls[1], 5.0)] {
+        let mut ball = app.world_mut().entity_mut(entity);
+        ball.get_mut::<Position>().unwrap().0 = Vec2::new(x, 0.0);
+        ball.get_mut::<Velocity>().unwrap().0 = Vec2::ZERO;
+    }
+    common::advance(&mut app, 30);
+
+    let distance = app.world().get::<Position>(balls[0]).unwrap()
+        .distance(**app.world().get::<Position>(balls[1]).unwrap());
+    assert!(distance > 39.0, "{distance}");
+    assert_eq!(momentum(&mut app), Vec2::ZERO);
+}
+
+#[test]
+fn balls_in_other_layers_pass_through() {
+    let mut app = common::demo_app(Demo::Collision, CollisionDemoPlugin);
+
+    // 弾はプレイヤーをすり抜けるが、他のボールとは衝突する
+    let player = CollisionLayers::new(0b01, CollisionLayers::ALL & !0b10);
+    let bullet = CollisionLayers::new(0b10, CollisionLayers::ALL & !0b01);
+    let mut query = app.world_mut().query_filtered::<Entity, With<Ball>>();
+    let balls: Vec<Entity> = query.iter(app.world()).collect();
+    for entity in &balls[2..] {
+        app.world_mut().despawn(*entity);
+    }
+    for (entity, x, speed, layers) in [(balls[0], -15.0, 80.0, player), (balls[1], 15.0, -80.0, bullet)] {
+        let mut ball = app.world_mut().entity_mut(entity);
+        ball.get_mut::<Position>().unwrap().0 = Vec2::new(x, 0.0);
+        ball.get_mut::<Velocity>().unwrap().0 = Vec2::new(speed, 0.0);
+        ball.insert(layers);
+    }
+    common::advance(&mut app, 10);
+
+    assert_eq!(app.world().get::<Velocity>(balls[0]).unwrap().0, Vec2::new(80.0, 0.0));
+    assert_eq!(app.world().get::<Velocity>(balls[1]).unwrap().0, Vec2::new(-80.0, 0.0));
+}
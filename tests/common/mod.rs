@@ -0,0 +1,123 @@
+#![allow(dead_code)]
+
+use std::{
+    env,
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use bevy::{
+    prelude::*,
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput, NativeKey, NativeKeyCode},
+        mouse::MouseButtonInput,
+    },
+};
+
+use example_bevy::{ExampleBasePlugin, demos::Demo, physics::PhysicsPlugin};
+
+const TEST_LOG_FILTER: &str = "warn";
+
+/// アプリごとに別の保存先を使うための番号
+static NEXT_STORAGE_ROOT: AtomicUsize = AtomicUsize::new(0);
+
+/// 指定したティック数だけヘッドレスモードで実行する共通プラグインを返す関数
+/// テストの出力が読みやすいように、ログは警告以上だけを表示する
+/// ハイスコアや音の設定は、アプリごとの一時フォルダに保存する
+pub fn base_plugin(ticks: u32) -> ExampleBasePlugin {
+    let root = temp_path(&format!("storage-{}", NEXT_STORAGE_ROOT.fetch_add(1, Ordering::Relaxed)));
+    ExampleBasePlugin {
+        log_filter: TEST_LOG_FILTER.to_string(),
+        ..ExampleBasePlugin::new("test")
+    }.headless(ticks).storage_root(root)
+}
+
+/// サンプルのシステムを組み込んだテスト用のアプリを作成する関数
+/// ヘッドレスモードで動き、1回の更新で固定タイムステップ1回分だけ時間が進む
+/// 返す前に1回更新して、サンプルのセットアップを実行しておく
+pub fn demo_app<M>(demo: Demo, plugins: impl bevy::app::Plugins<M>) -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(base_plugin(u32::MAX))
+        .insert_state(demo)
+        .add_plugins(plugins);
+    app.finish();
+    app.cleanup();
+    app.update();
+    app
+}
+
+/// 物理演算のプラグインだけを組み込んだテスト用のアプリを作成する関数
+pub fn physics_app() -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(base_plugin(u32::MAX))
+        .add_plugins(PhysicsPlugin);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// 指定したティック数だけアプリを更新する関数
+pub fn advance(app: &mut App, ticks: u32) {
+    for _ in 0..ticks {
+        app.update();
+    }
+}
+
+/// キーを押して離す関数
+/// 押したフレームで`just_pressed`になり、ステート遷移は次のフレームで反映される
+pub fn press_key(app: &mut App, key_code: KeyCode) {
+    press_logical_key(app, key_code, Key::Unidentified(NativeKey::Unidentified));
+}
+
+/// 論理キーを指定してキーを押して離す関数
+pub fn press_logical_key(app: &mut App, key_code: KeyCode, logical_key: Key) {
+    let keyboard_input = |state| KeyboardInput {
+        key_code,
+        logical_key: logical_key.clone(),
+        state,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    };
+    app.world_mut().send_event(keyboard_input(ButtonState::Pressed));
+    app.update();
+    app.world_mut().send_event(keyboard_input(ButtonState::Released));
+    app.update();
+}
+
+/// 1文字ずつキーを押して離し、文字を入力する関数
+pub fn type_text(app: &mut App, text: &str) {
+    for character in text.chars() {
+        press_logical_key(app, KeyCode::Unidentified(NativeKeyCode::Unidentified), Key::Character(character.to_string().into()));
+    }
+}
+
+/// 一時フォルダの中に、テストごとに別のファイルのパスを返す関数
+pub fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir()
+        .join(format!("example-bevy-test-{}", process::id()))
+        .join(name)
+}
+
+/// マウスのボタンを押して離す関数
+pub fn click_mouse(app: &mut App, button: MouseButton) {
+    let mouse_input = |state| MouseButtonInput {
+        button,
+        state,
+        window: Entity::PLACEHOLDER,
+    };
+    app.world_mut().send_event(mouse_input(ButtonState::Pressed));
+    app.update();
+    app.world_mut().send_event(mouse_input(ButtonState::Released));
+    app.update();
+}
+
+/// 条件に合うエンティティの数を返す関数
+pub fn count<F: bevy::ecs::query::QueryFilter>(app: &mut App) -> usize {
+    let mut query = app.world_mut().query_filtered::<(), F>();
+    query.iter(app.world()).count()
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use bevy::{gizmos::GizmoPlugin, prelude::*, render::render_resource::Shader};
+
+use example_bevy::physics::{
+    Collider,
+    CollisionLayers,
+    PhysicsDebug,
+    PhysicsPlugin,
+    Velocity,
+    debug::{KEY_TOGGLE_PHYSICS_DEBUG, layer_color},
+};
+
+#[test]
+fn hotkey_toggles_debug_overlay() {
+    let mut app = common::physics_app();
+    assert!(!app.world().resource::<PhysicsDebug>().enabled);
+
+    common::press_key(&mut app, KEY_TOGGLE_PHYSICS_DEBUG);
+    assert!(app.world().resource::<PhysicsDebug>().enabled);
+
+    common::press_key(&mut app, KEY_TOGGLE_PHYSICS_DEBUG);
+    assert!(!app.world().resource::<PhysicsDebug>().enabled);
+}
+
+#[test]
+fn layers_have_distinct_colors() {
+    let colors: Vec<Srgba> = (0..4)
+        .map(|bit| layer_color(&CollisionLayers::new(1 << bit, CollisionLayers::ALL)))
+        .collect();
+    for (i, color) in colors.iter().enumerate() {
+        assert!(colors[i + 1..].iter().all(|other| other != color), "{colors:?}");
+    }
+    assert_eq!(layer_color(&CollisionLayers::default()), layer_color(&CollisionLayers::new(0, 0)));
+}
+
+#[test]
+fn overlay_draws_every_collider_shape() {
+    let mut app = App::new();
+    app
+        .add_plugins(common::base_plugin(u32::MAX))
+        // ギズモのプラグインが使うアセットを登録
+        .init_asset::<Shader>()
+        .add_plugins((GizmoPlugin, PhysicsPlugin));
+    app.finish();
+    app.cleanup();
+    app.world_mut().resource_mut::<PhysicsDebug>().enabled = true;
+    let shapes = [
+        Collider::circle(10.0),
+        Collider::rectangle(20.0, 10.0),
+        Collider::oriented_rectangle(20.0, 10.0),
+        Collider::capsule(5.0, 20.0),
+        Collider::segment(Vec2::ZERO, Vec2::X * 20.0),
+        Collider::polygon(&[Vec2::ZERO, Vec2::X * 20.0, Vec2::Y * 20.0]).unwrap(),
+    ];
+    for (i, shape) in shapes.into_iter().enumerate() {
+        app.world_mut().spawn((
+            Transform::from_xyz(i as f32 * 15.0, 0.0, 0.0),
+            shape,
+            Velocity(Vec2::X),
+        ));
+    }
+
+    // 重なっている形状の接触も含めて、描いてもパニックしない
+    common::advance(&mut app, 3);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::{
+    demos::{Demo, collision::CollisionDemoPlugin},
+    physics::{Ccd, Collider, DeterministicMode, Position, StableId, Velocity, determinism::state_hash},
+};
+
+/// 押し合うボールの列と行の数
+const CLUSTER_SIZE: u32 = 5;
+
+/// 衝突のサンプルを決定論的モードで動かし、状態のハッシュを返す関数
+fn run_collision_demo(ticks: u32) -> u64 {
+    let mut app = common::demo_app(Demo::Collision, CollisionDemoPlugin);
+    app.insert_resource(DeterministicMode(true));
+    common::advance(&mut app, ticks);
+    state_hash(app.world_mut())
+}
+
+#[test]
+fn collision_demo_is_bit_identical_across_runs() {
+    let first = run_collision_demo(10_000);
+    let second = run_collision_demo(10_000);
+    assert_eq!(first, second);
+
+    // ハッシュが状態によって変わることも確かめる
+    assert_ne!(first, run_collision_demo(100));
+}
+
+#[test]
+fn stable_ids_are_assigned_in_spawn_order() {
+    let mut app = common::physics_app();
+    let first = app.world_mut().spawn(Collider::circle(1.0)).id();
+    let second = app.world_mut().spawn(Collider::circle(1.0)).id();
+    let replayed = app.world_mut().spawn((Collider::circle(1.0), StableId(100))).id();
+    let third = app.world_mut().spawn(Collider::circle(1.0)).id();
+
+    let id = |entity| **app.world().get::<StableId>(entity).unwrap();
+    assert_eq!([id(first), id(second), id(replayed), id(third)], [1, 2, 100, 3]);
+}
+
+/// 決定論的モードの物理演算のアプリを作成する関数
+/// `churn`の場合は、生成と削除を繰り返して、Entityの番号とクエリの順番をずらしておく
+fn deterministic_app(churn: bool) -> App {
+    let mut app = common::physics_app();
+    app.insert_resource(DeterministicMode(true));
+    if churn {
+        for _ in 0..3 {
+            let entities: Vec<Entity> = (0..7).map(|_| app.world_mut().spawn(Collider::circle(1.0)).id()).collect();
+            for entity in entities.into_iter().step_by(2) {
+                app.world_mut().despawn(entity);
+            }
+        }
+        let leftovers: Vec<Entity> = app.world_mut().query_filtered::<Entity, With<Collider>>().iter(app.world()).collect();
+        for entity in leftovers {
+            app.world_mut().despawn(entity);
+        }
+    }
+    app
+}
+
+/// 重なり合ったボールの塊を、指定した順番で生成して動かし、状態のハッシュを返す関数
+/// 保存したIDで生成し直すリプレイと同じく、安定したIDは順番に関係なく揃える
+fn run_cluster(reversed: bool, churn: bool) -> u64 {
+    let mut app = deterministic_app(churn);
+    let mut indices: Vec<u32> = (0..CLUSTER_SIZE * CLUSTER_SIZE).collect();
+    if reversed {
+        indices.reverse();
+    }
+    for i in indices {
+        let (x, y) = ((i % CLUSTER_SIZE) as f32, (i / CLUSTER_SIZE) as f32);
+        let center = (CLUSTER_SIZE - 1) as f32 / 2.0;
+        app.world_mut().spawn((
+            Transform::from_xyz((x - center) * 8.0, (y - center) * 8.0, 0.0),
+            Collider::circle(5.0),
+            Velocity(Vec2::new((center - x) * 37.0 + y, (center - y) * 23.0 - x)),
+            StableId(u64::from(i) + 1),
+        ));
+    }
+    common::advance(&mut app, 300);
+    state_hash(app.world_mut())
+}
+
+/// 速いボールが、接した2つのボールの間に同じ時刻に当たる場面を、指定した順番で生成して動かし、状態のハッシュを返す関数
+fn run_ccd_tie(reversed: bool, churn: bool) -> u64 {
+    let mut app = deterministic_app(churn);
+    let mut bodies = vec![
+        (Transform::from_xyz(-60.0, 0.0, 0.0), Velocity(Vec2::new(6000.0, 0.0)), StableId(1)),
+        (Transform::from_xyz(20.0, 5.0, 0.0), Velocity(Vec2::new(-600.0, 0.0)), StableId(2)),
+        (Transform::from_xyz(20.0, -5.0, 0.0), Velocity(Vec2::new(-600.0, 0.0)), StableId(3)),
+    ];
+    if reversed {
+        bodies.reverse();
+    }
+    for (transform, velocity, id) in bodies {
+        let mut body = app.world_mut().spawn((transform, Collider::circle(5.0), velocity, id));
+        if *id == 1 {
+            body.insert(Ccd);
+        }
+    }
+    common::advance(&mut app, 30);
+    state_hash(app.world_mut())
+}
+
+#[test]
+fn spawn_order_does_not_change_deterministic_result() {
+    let expected = run_cluster(false, false);
+    assert_eq!(run_cluster(true, false), expected);
+    assert_eq!(run_cluster(true, true), expected);
+    assert_eq!(run_cluster(false, true), expected);
+}
+
+#[test]
+fn spawn_order_does_not_change_ccd_result() {
+    let expected = run_ccd_tie(false, false);
+    assert_eq!(run_ccd_tie(true, false), expected);
+    assert_eq!(run_ccd_tie(true, true), expected);
+    assert_eq!(run_ccd_tie(false, true), expected);
+}
+
+#[test]
+fn state_hash_is_pinned_across_toolchains() {
+    let mut world = World::new();
+    // 位置は`Transform`から初期化される
+    world.spawn((StableId(2), Transform::from_xyz(-3.0, 0.25, 0.0), Velocity(Vec2::new(60.0, -1.5))));
+    world.spawn((StableId(1), Transform::from_xyz(1.0, 2.0, 0.0), Position::default()));
+    // FNV-1aで計算した値なので、ツールチェーンが変わっても同じになる
+    assert_eq!(state_hash(&mut world), 17_067_526_414_336_854_446);
+}
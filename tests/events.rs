@@ -0,0 +1,103 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::{
+    WINDOW_SIZE,
+    demos::{Demo, velocity::VelocityDemoPlugin},
+    physics::{Collider, CollisionEnded, CollisionStarted, Position, Velocity, WallHit, WallSide},
+};
+
+/// 受け取ったイベントを記録するリソース
+#[derive(Resource, Default)]
+struct Received {
+    started: Vec<CollisionStarted>,
+    ended: Vec<CollisionEnded>,
+    /// オブザーバーに通知されたエンティティ
+    targets: Vec<Entity>,
+}
+
+/// バッファ付きのイベントを記録する関数
+fn record_events(
+    mut started: EventReader<CollisionStarted>,
+    mut ended: EventReader<CollisionEnded>,
+    mut received: ResMut<Received>,
+) {
+    received.started.extend(started.read().copied());
+    received.ended.extend(ended.read().copied());
+}
+
+/// 向かい合って近づく2つの円を生成したアプリを作成する関数
+fn approaching_circles() -> (App, Entity, Entity) {
+    let mut app = common::physics_app();
+    app
+        .init_resource::<Received>()
+        .add_systems(FixedPostUpdate, record_events);
+    let observe = |trigger: Trigger<CollisionStarted>, mut received: ResMut<Received>| {
+        received.targets.push(trigger.target());
+    };
+    let left = app.world_mut().spawn((
+        Transform::from_xyz(-15.0, 0.0, 0.0),
+        Collider::circle(10.0),
+        Velocity(Vec2::new(60.0, 0.0)),
+    )).observe(observe).id();
+    let right = app.world_mut().spawn((
+        Transform::from_xyz(15.0, 0.0, 0.0),
+        Collider::circle(10.0),
+        Velocity(Vec2::new(-60.0, 0.0)),
+    )).observe(observe).id();
+    app.update();
+    (app, left, right)
+}
+
+#[test]
+fn collision_starts_and_ends_once() {
+    let (mut app, left, right) = approaching_circles();
+
+    // 5ティックで触れ、跳ね返って離れる
+    common::advance(&mut app, 30);
+    let received = app.world().resource::<Received>();
+    assert_eq!(received.started.len(), 1);
+    assert_eq!(received.ended.len(), 1);
+
+    let started = received.started[0];
+    assert_eq!(started.other(left), right);
+    assert_eq!(started.other(right), left);
+    // 法線は1つ目のエンティティから2つ目のエンティティへ向かう
+    let direction = if started.entity1 == left { Vec2::X } else { Vec2::NEG_X };
+    assert!(started.normal.distance(direction) < 1e-3, "{}", started.normal);
+    assert!(started.point.x.abs() < 1.0, "{}", started.point);
+}
+
+#[test]
+fn collision_started_triggers_both_entities() {
+    let (mut app, left, right) = approaching_circles();
+
+    common::advance(&mut app, 30);
+    let mut targets = app.world().resource::<Received>().targets.clone();
+    targets.sort();
+    let mut expected = vec![left, right];
+    expected.sort();
+    assert_eq!(targets, expected);
+}
+
+#[test]
+fn wall_hit_is_sent_once() {
+    let mut app = common::demo_app(Demo::Velocity, VelocityDemoPlugin);
+    app
+        .init_resource::<Received>()
+        .add_observer(|trigger: Trigger<WallHit>, mut received: ResMut<Received>| {
+            assert_eq!(trigger.side, WallSide::Right);
+            assert_eq!(trigger.normal, Vec2::NEG_X);
+            assert_eq!(trigger.point.x, WINDOW_SIZE.x / 2.0);
+            received.targets.push(trigger.target());
+        });
+
+    // 右端にはみ出したまま数ティック進めても、当たるのは1回だけ
+    let mut query = app.world_mut().query_filtered::<(Entity, &mut Position), With<Velocity>>();
+    let (square, mut position) = query.single_mut(app.world_mut()).unwrap();
+    position.x = WINDOW_SIZE.x / 2.0;
+    common::advance(&mut app, 5);
+
+    assert_eq!(app.world().resource::<Received>().targets, [square]);
+}
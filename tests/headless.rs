@@ -0,0 +1,126 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::{
+    demos::{
+        Demo,
+        app_state::{AppState, AppStateDemoPlugin, Mainmenu},
+        audio::AudioDemoPlugin,
+        collision::{Ball, CollisionDemoPlugin},
+        scoreboard::{PlayerInput, ScoreboardDemoPlugin},
+        setup::SetupDemoPlugin,
+        spritesheet::SpritesheetDemoPlugin,
+        ui::UiDemoPlugin,
+        velocity::VelocityDemoPlugin,
+    },
+    physics::Velocity,
+    launcher::LauncherPlugin,
+    score::Score,
+    ExampleBasePlugin,
+    HEADLESS_TICKS,
+    parse_headless_ticks,
+};
+
+const TICKS: u32 = 120;
+
+/// ヘッドレスモードのアプリを終了するまで更新する関数
+/// `App::run`は実行後のワールドを返さないため、ランナーと同じ処理を直接行う
+fn run_until_exit(app: &mut App) {
+    app.finish();
+    app.cleanup();
+    while app.should_exit().is_none() {
+        app.update();
+    }
+    assert_eq!(app.should_exit(), Some(AppExit::Success));
+}
+
+/// 指定したサンプルをヘッドレスモードで実行し、終了後のアプリを返す関数
+fn run_headless(demo: Demo, plugin: impl Plugin) -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(common::base_plugin(TICKS))
+        .insert_state(demo)
+        .add_plugins(plugin);
+    run_until_exit(&mut app);
+    app
+}
+
+#[test]
+fn velocity_moves_square() {
+    let mut app = run_headless(Demo::Velocity, VelocityDemoPlugin);
+    let mut query = app.world_mut().query_filtered::<&Transform, With<Velocity>>();
+    let transform = query.single(app.world()).unwrap();
+    assert_ne!(transform.translation, Vec3::ZERO);
+}
+
+#[test]
+fn collision_spawns_balls() {
+    let mut app = run_headless(Demo::Collision, CollisionDemoPlugin);
+    let mut query = app.world_mut().query_filtered::<(), With<Ball>>();
+    assert_eq!(query.iter(app.world()).count(), 9);
+}
+
+#[test]
+fn scoreboard_starts_at_zero() {
+    let mut app = run_headless(Demo::Scoreboard, ScoreboardDemoPlugin);
+    let mut query = app.world_mut().query_filtered::<&Score, With<PlayerInput>>();
+    assert!(query.iter(app.world()).all(|score| **score == 0));
+    assert_eq!(query.iter(app.world()).count(), 2);
+}
+
+#[test]
+fn app_state_starts_in_mainmenu() {
+    let mut app = run_headless(Demo::AppState, AppStateDemoPlugin);
+    assert_eq!(*app.world().resource::<State<AppState>>().get(), AppState::Mainmenu);
+    let mut query = app.world_mut().query_filtered::<(), With<Mainmenu>>();
+    assert_eq!(query.iter(app.world()).count(), 1);
+}
+
+#[test]
+fn other_demos_run_headless() {
+    run_headless(Demo::Audio, AudioDemoPlugin);
+    run_headless(Demo::Spritesheet, SpritesheetDemoPlugin);
+    run_headless(Demo::Ui, UiDemoPlugin);
+    run_headless(Demo::Setup, SetupDemoPlugin);
+}
+
+#[test]
+fn launcher_runs_headless() {
+    let mut app = App::new();
+    app
+        .add_plugins(common::base_plugin(TICKS))
+        .add_plugins(LauncherPlugin);
+    run_until_exit(&mut app);
+    assert_eq!(*app.world().resource::<State<Demo>>().get(), Demo::Menu);
+}
+
+#[test]
+fn headless_ticks_reject_invalid_values() {
+    assert_eq!(parse_headless_ticks("").unwrap(), HEADLESS_TICKS);
+    assert_eq!(parse_headless_ticks("120").unwrap(), 120);
+    assert!(parse_headless_ticks("abc").is_err());
+    assert!(parse_headless_ticks("-1").is_err());
+}
+
+#[test]
+fn invalid_headless_value_is_reported_without_panicking() {
+    // 不正な値はエラーとしてログに出し、パニックせずに実行する
+    let mut app = App::new();
+    app
+        .add_plugins(ExampleBasePlugin {
+            headless_error: Some("invalid headless tick count \"abc\"".to_string()),
+            ..common::base_plugin(TICKS)
+        })
+        .insert_state(Demo::Velocity)
+        .add_plugins(VelocityDemoPlugin);
+    run_until_exit(&mut app);
+
+    // ティック数を指定し直すと、エラーは使われないので消える
+    let plugin = ExampleBasePlugin {
+        headless_error: Some("invalid".to_string()),
+        ..ExampleBasePlugin::new("test")
+    }.headless(TICKS);
+    assert_eq!(plugin.headless, Some(TICKS));
+    assert!(plugin.headless_error.is_none());
+}
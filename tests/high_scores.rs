@@ -0,0 +1,96 @@
+mod common;
+
+use std::fs;
+
+use bevy::prelude::*;
+
+use example_bevy::{
+    score::{HighScoreEntry, HighScores, high_scores::{HIGH_SCORES_FILE, backup_path, format_date}},
+    storage::{DATA_DIR, StorageRoot},
+};
+
+/// 日付を固定した記録を作成する関数
+fn entry(name: &str, score: usize) -> HighScoreEntry {
+    HighScoreEntry {
+        name: name.to_string(),
+        score,
+        date: "2026-01-01".to_string(),
+    }
+}
+
+#[test]
+fn keeps_top_entries_in_order() {
+    let mut high_scores = HighScores::new(3, None);
+    assert!(!high_scores.qualifies(0));
+    assert_eq!(high_scores.insert(entry("A", 10)), Some(0));
+    assert_eq!(high_scores.insert(entry("B", 30)), Some(0));
+    // 同じスコアは先に記録した方が上位
+    assert_eq!(high_scores.insert(entry("C", 10)), Some(2));
+    assert_eq!(high_scores.insert(entry("D", 20)), Some(1));
+
+    let names: Vec<&str> = high_scores.entries().iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(names, ["B", "D", "A"]);
+
+    // 最下位以下のスコアは残らない
+    assert!(!high_scores.qualifies(10));
+    assert_eq!(high_scores.insert(entry("E", 5)), None);
+    assert!(high_scores.qualifies(11));
+}
+
+#[test]
+fn saves_and_loads_from_file() {
+    let path = common::temp_path("high_scores/save_and_load.ron");
+    let _ = fs::remove_file(&path);
+
+    // ファイルが無い場合は空の記録になる
+    let mut high_scores = HighScores::load(&path, 5).unwrap();
+    assert!(high_scores.entries().is_empty());
+
+    high_scores.insert(entry("みさき", 120));
+    high_scores.insert(entry("BEVY", 80));
+    high_scores.save().unwrap();
+
+    let loaded = HighScores::load(&path, 5).unwrap();
+    assert_eq!(loaded, high_scores);
+
+    // 件数が少ない場合は上位だけを読み込む
+    let fewer = HighScores::load(&path, 1).unwrap();
+    assert_eq!(fewer.entries(), [entry("みさき", 120)]);
+}
+
+#[test]
+fn broken_file_is_an_error() {
+    let path = common::temp_path("high_scores/broken.ron");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "not ron").unwrap();
+    assert!(HighScores::load(&path, 5).is_err());
+}
+
+#[test]
+fn broken_file_is_moved_aside_before_saving() {
+    let root = common::temp_path("high_scores/broken_root");
+    let path = root.join(DATA_DIR).join(HIGH_SCORES_FILE);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "not ron").unwrap();
+
+    let mut world = World::new();
+    world.insert_resource(StorageRoot(root));
+    let mut high_scores = HighScores::from_world(&mut world);
+    assert!(high_scores.entries().is_empty());
+    assert_eq!(high_scores.path(), Some(path.as_path()));
+    assert!(!path.exists());
+
+    // 新しい記録を保存しても、読み込めなかったファイルは残る
+    high_scores.insert(entry("A", 10));
+    high_scores.save().unwrap();
+    assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "not ron");
+    assert_eq!(HighScores::load(&path, 5).unwrap().entries(), [entry("A", 10)]);
+}
+
+#[test]
+fn formats_unix_time_as_date() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(1_700_000_000), "2023-11-14");
+    assert!(HighScoreEntry::new("A", 1).date.starts_with("20"));
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use example_bevy::physics::{
+    Acceleration,
+    AngularVelocity,
+    Gravity,
+    LinearDamping,
+    MaxSpeed,
+    Position,
+    PreviousPosition,
+    Rotation,
+    Velocity,
+};
+
+const SPEED: f32 = 60.0;
+// 半分に割り切れるように、ナノ秒単位で偶数のタイムステップを使う
+const TIMESTEP: Duration = Duration::from_millis(20);
+const STEP: f32 = SPEED * 0.02;
+
+/// 1回の更新で進む時間を固定タイムステップの半分にしたアプリを作成する関数
+fn half_step_app() -> (App, Entity) {
+    let mut app = common::physics_app();
+    app
+        .insert_resource(Time::<Fixed>::from_duration(TIMESTEP))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TIMESTEP / 2));
+    let entity = app.world_mut().spawn((
+        Transform::from_xyz(10.0, 20.0, 3.0),
+        Velocity(Vec2::new(SPEED, 0.0)),
+    )).id();
+    // 初回の更新は時間が進まない
+    app.update();
+    (app, entity)
+}
+
+/// 1回の更新で固定タイムステップ1回分だけ進むアプリに、エンティティを1つ生成する関数
+fn full_step_app(bundle: impl Bundle) -> (App, Entity) {
+    let mut app = common::physics_app();
+    app
+        .insert_resource(Time::<Fixed>::from_duration(TIMESTEP))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TIMESTEP));
+    let entity = app.world_mut().spawn(bundle).id();
+    // 初回の更新は時間が進まない
+    app.update();
+    (app, entity)
+}
+
+#[test]
+fn position_is_initialized_from_transform() {
+    let (app, entity) = half_step_app();
+    assert_eq!(**app.world().get::<Position>(entity).unwrap(), Vec2::new(10.0, 20.0));
+    assert_eq!(**app.world().get::<PreviousPosition>(entity).unwrap(), Vec2::new(10.0, 20.0));
+}
+
+#[test]
+fn position_advances_only_on_fixed_steps() {
+    let (mut app, entity) = half_step_app();
+
+    app.update();
+    assert_eq!(app.world().get::<Position>(entity).unwrap().x, 10.0);
+    app.update();
+    assert!((app.world().get::<Position>(entity).unwrap().x - (10.0 + STEP)).abs() < 1e-4);
+}
+
+#[test]
+fn transform_is_interpolated_between_fixed_steps() {
+    let (mut app, entity) = half_step_app();
+
+    // 2回で1ステップ進み、3回目は次のステップの半分まで時間が進む
+    common::advance(&mut app, 3);
+    let transform = app.world().get::<Transform>(entity).unwrap();
+    assert!((transform.translation.x - (10.0 + STEP * 0.5)).abs() < 1e-4, "{}", transform.translation);
+    assert_eq!(transform.translation.y, 20.0);
+    assert_eq!(transform.translation.z, 3.0);
+}
+
+#[test]
+fn acceleration_changes_velocity() {
+    let (mut app, entity) = full_step_app(Acceleration(Vec2::new(0.0, SPEED)));
+
+    common::advance(&mut app, 10);
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert!((velocity.y - SPEED * 0.2).abs() < 1e-3, "{}", **velocity);
+}
+
+#[test]
+fn entity_gravity_overrides_global_gravity() {
+    let (mut app, entity) = full_step_app(Velocity::default());
+    let local = app.world_mut().spawn((Velocity::default(), Gravity(Vec2::new(SPEED, 0.0)))).id();
+    app.insert_resource(Gravity(Vec2::new(0.0, -SPEED)));
+
+    common::advance(&mut app, 10);
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert!((velocity.y + SPEED * 0.2).abs() < 1e-3, "{}", **velocity);
+    let velocity = app.world().get::<Velocity>(local).unwrap();
+    assert!((velocity.x - SPEED * 0.2).abs() < 1e-3, "{}", **velocity);
+    assert_eq!(velocity.y, 0.0);
+}
+
+#[test]
+fn damping_slows_velocity_down() {
+    let (mut app, entity) = full_step_app((
+        Velocity(Vec2::new(SPEED, 0.0)),
+        LinearDamping(1.0),
+    ));
+
+    common::advance(&mut app, 50);
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert!(velocity.x > 0.0 && velocity.x < SPEED * 0.5, "{}", **velocity);
+}
+
+#[test]
+fn speed_is_clamped_to_max_speed() {
+    let (mut app, entity) = full_step_app((
+        Acceleration(Vec2::new(SPEED * 100.0, 0.0)),
+        MaxSpeed(SPEED),
+    ));
+
+    common::advance(&mut app, 10);
+    let velocity = app.world().get::<Velocity>(entity).unwrap();
+    assert!((velocity.length() - SPEED).abs() < 1e-3, "{}", **velocity);
+}
+
+#[test]
+fn angular_velocity_rotates_transform() {
+    let (mut app, entity) = full_step_app(AngularVelocity(std::f32::consts::PI));
+
+    // 0.5秒で90度回転する
+    common::advance(&mut app, 25);
+    let rotation = app.world().get::<Rotation>(entity).unwrap();
+    assert!((**rotation - std::f32::consts::FRAC_PI_2).abs() < 1e-3, "{}", **rotation);
+    // 時間が固定タイムステップちょうどなので、描画は1つ前のステップの回転になる
+    let transform = app.world().get::<Transform>(entity).unwrap();
+    let angle = transform.rotation.to_euler(EulerRot::ZYX).0;
+    let expected = std::f32::consts::FRAC_PI_2 - std::f32::consts::PI * 0.02;
+    assert!((angle - expected).abs() < 1e-3, "{angle}");
+}
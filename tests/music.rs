@@ -0,0 +1,107 @@
+use bevy::{
+    prelude::*,
+    asset::uuid::Uuid,
+};
+
+use example_bevy::audio::{MusicPlayer, RepeatMode};
+
+/// 番号で区別できる曲を返す関数
+fn track(id: u128) -> Handle<AudioSource> {
+    Handle::Weak(AssetId::Uuid { uuid: Uuid::from_u128(id) })
+}
+
+/// 3曲のプレイリストのプレイヤーを作成する関数
+fn player() -> MusicPlayer {
+    MusicPlayer::new((0..3).map(track)).with_seed(7)
+}
+
+#[test]
+fn next_and_previous_wrap_around() {
+    let mut player = player();
+    assert!(!player.is_playing());
+    player.play();
+    assert_eq!(player.current_index(), Some(0));
+    assert_eq!(player.current_track(), Some(&track(0)));
+
+    player.next();
+    player.next();
+    assert_eq!(player.current_index(), Some(2));
+    player.next();
+    assert_eq!(player.current_index(), Some(0));
+    player.previous();
+    assert_eq!(player.current_index(), Some(2));
+
+    // 止めても、同じ曲から再生し直せる
+    player.stop();
+    assert_eq!(player.current_index(), None);
+    player.play();
+    assert_eq!(player.current_index(), Some(2));
+}
+
+#[test]
+fn repeat_modes_decide_next_track() {
+    let mut player = player();
+    player.play_index(2);
+
+    player.repeat = RepeatMode::One;
+    player.finish_track();
+    assert_eq!(player.current_index(), Some(2));
+
+    player.repeat = RepeatMode::All;
+    player.finish_track();
+    assert_eq!(player.current_index(), Some(0));
+
+    player.repeat = RepeatMode::Off;
+    player.finish_track();
+    assert_eq!(player.current_index(), Some(1));
+    player.play_index(2);
+    player.finish_track();
+    assert!(!player.is_playing());
+
+    assert_eq!(RepeatMode::Off.cycle(), RepeatMode::All);
+    assert_eq!(RepeatMode::All.cycle(), RepeatMode::One);
+    assert_eq!(RepeatMode::One.cycle(), RepeatMode::Off);
+}
+
+#[test]
+fn shuffle_plays_every_track_once_per_round() {
+    let mut player = MusicPlayer::new((0..8).map(track)).with_seed(1);
+    player.play_index(5);
+    player.set_shuffle(true);
+    assert!(player.shuffle());
+    // 再生中の曲はそのまま
+    assert_eq!(player.current_index(), Some(5));
+
+    let mut played = vec![5];
+    for _ in 0..7 {
+        player.next();
+        played.push(player.current_index().unwrap());
+    }
+    let mut sorted = played.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..8).collect::<Vec<_>>());
+    assert_ne!(played, vec![5, 6, 7, 0, 1, 2, 3, 4]);
+
+    // シャッフルをやめると、プレイリストの順番に戻る
+    let current = player.current_index().unwrap();
+    player.set_shuffle(false);
+    player.next();
+    assert_eq!(player.current_index(), Some((current + 1) % 8));
+}
+
+#[test]
+fn play_track_outside_playlist() {
+    let mut player = player();
+    player.play();
+    player.play_track(track(10), false);
+    assert_eq!(player.current_track(), Some(&track(10)));
+    assert_eq!(player.current_index(), None);
+
+    // 繰り返さない曲は、終わったら止まる
+    player.finish_track();
+    assert!(!player.is_playing());
+
+    // プレイリストは最初の曲から再生し直す
+    player.play();
+    assert_eq!(player.current_index(), Some(0));
+}
@@ -0,0 +1,39 @@
+use example_bevy::score::{ScoreFormat, ScoreSource, Scoreboard};
+
+#[test]
+fn formats_numbers() {
+    let plain = ScoreFormat::default();
+    assert_eq!(plain.format(1234567), "1234567");
+    let separated = ScoreFormat::default().with_separator(',');
+    assert_eq!(separated.format(0), "0");
+    assert_eq!(separated.format(999), "999");
+    assert_eq!(separated.format(1000), "1,000");
+    assert_eq!(separated.format(1234567), "1,234,567");
+    assert_eq!(ScoreFormat::default().zero_padded(6).format(42), "000042");
+    assert_eq!(ScoreFormat::default().zero_padded(6).with_separator(',').format(42), "000,042");
+}
+
+#[test]
+fn formats_full_width_digits() {
+    let format = ScoreFormat::default().with_separator(',').full_width();
+    assert_eq!(format.format(12345), "１２，３４５");
+    assert_eq!(ScoreFormat::time().full_width().format(75), "０１：１５");
+}
+
+#[test]
+fn formats_time() {
+    let format = ScoreFormat::time();
+    assert_eq!(format.format(0), "00:00");
+    assert_eq!(format.format(59), "00:59");
+    assert_eq!(format.format(61), "01:01");
+    assert_eq!(format.format(6000), "100:00");
+}
+
+#[test]
+fn boards_accept_named_or_listed_sources() {
+    let board = Scoreboard::new("1P").with_sources([ScoreSource::Pickup]);
+    assert!(board.accepts(Some("1P"), &ScoreSource::Input));
+    assert!(!board.accepts(Some("2P"), &ScoreSource::Pickup));
+    assert!(board.accepts(None, &ScoreSource::Pickup));
+    assert!(!board.accepts(None, &ScoreSource::Input));
+}
@@ -0,0 +1,59 @@
+use example_bevy::score::{BonusRule, MAX_MULTIPLIER_LIMIT, ScoreRules, ScoreRulesError, ScoreSource};
+
+#[test]
+fn parses_rules_with_defaults() {
+    let rules = ScoreRules::from_ron(br#"(
+        combo_step: 3,
+        bonuses: [(source: Some(Custom("coin")), every_combo: 4, points: 7)],
+    )"#).unwrap();
+    assert_eq!(rules.combo_step, 3);
+    assert_eq!(rules.combo_window, ScoreRules::default().combo_window);
+    assert_eq!(rules.bonuses, [BonusRule {
+        source: Some(ScoreSource::Custom("coin".to_string())),
+        every_combo: 4,
+        points: 7,
+    }]);
+    assert_eq!(rules.bonus(&ScoreSource::Custom("coin".to_string()), 8), 7);
+    assert_eq!(rules.bonus(&ScoreSource::Input, 8), 0);
+}
+
+#[test]
+fn multiplier_is_capped() {
+    let rules = ScoreRules::default();
+    let multipliers: Vec<u32> = [0, 1, 5, 6, 10, 11, 100].map(|combo| rules.multiplier(combo)).to_vec();
+    assert_eq!(multipliers, [1, 1, 1, 2, 2, 3, 4]);
+}
+
+#[test]
+fn rejects_invalid_rules() {
+    for source in [
+        "(combo_window: 0.0)",
+        "(combo_step: 0)",
+        "(max_multiplier: 0)",
+        "(max_multiplier: 101)",
+        "(bonuses: [(every_combo: 0, points: 1)])",
+    ] {
+        assert!(matches!(ScoreRules::from_ron(source.as_bytes()), Err(ScoreRulesError::Invalid { .. })), "{source}");
+    }
+    assert!(matches!(ScoreRules::from_ron(b"(unknown: 1)"), Err(ScoreRulesError::Ron(_))));
+}
+
+#[test]
+fn bonus_total_saturates() {
+    let rules = ScoreRules {
+        bonuses: vec![
+            BonusRule { source: None, every_combo: 1, points: usize::MAX },
+            BonusRule { source: None, every_combo: 1, points: 1 },
+        ],
+        max_multiplier: MAX_MULTIPLIER_LIMIT,
+        ..Default::default()
+    };
+    rules.validate().unwrap();
+    assert_eq!(rules.bonus(&ScoreSource::Input, 1), usize::MAX);
+}
+
+#[test]
+fn bundled_rules_are_valid() {
+    let bytes = std::fs::read("assets/config/score.rules.ron").unwrap();
+    ScoreRules::from_ron(&bytes).unwrap();
+}
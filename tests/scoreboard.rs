@@ -0,0 +1,363 @@
+mod common;
+
+use std::fs;
+
+use bevy::{input::keyboard::Key, prelude::*};
+
+use example_bevy::{
+    demos::{
+        Demo,
+        scoreboard::{
+            KEY_ADD_SCORE,
+            KEY_ADD_SCORE_PLAYER_TWO,
+            KEY_FINISH_ROUND,
+            KEY_LOSE_LIFE,
+            LIVES,
+            MAX_LIVES,
+            PLAYER_ONE,
+            PLAYER_TWO,
+            TIME,
+            ComboText,
+            HighScoreList,
+            NameEntry,
+            ScoreboardDemoPlugin,
+        },
+    },
+    score::{HighScores, RollingScore, Score, ScorePopup, ScoreAwarded, ScoreEvent, ScoreMultiplier, ScoreRules, ScoreSource, Scoreboard, BonusRule, MAX_MULTIPLIER_LIMIT},
+    score::high_scores::HIGH_SCORES_FILE,
+    storage::{DATA_DIR, StorageRoot},
+};
+
+/// 表示の数え上げが終わるまで待つ最大のティック数
+const MAX_ROLL_TICKS: u32 = 120;
+
+/// 名前を指定したスコアボードのエンティティを返す関数
+fn board(app: &mut App, name: &str) -> Entity {
+    let mut query = app.world_mut().query::<(Entity, &Scoreboard)>();
+    query.iter(app.world())
+        .find_map(|(entity, board)| (board.name == name).then_some(entity))
+        .unwrap()
+}
+
+/// スコアボードのコンボ数を返す関数
+fn combo(app: &mut App, name: &str) -> u32 {
+    let entity = board(app, name);
+    app.world().get::<ScoreMultiplier>(entity).unwrap().combo
+}
+
+/// スコアボードのコンボの表示を返す関数
+fn combo_text(app: &mut App, name: &str) -> String {
+    let mut query = app.world_mut().query::<(&Text, &ComboText)>();
+    query.iter(app.world()).find(|(_, combo)| combo.0 == name).unwrap().0.0.clone()
+}
+
+/// 1Pのスコアを返す関数
+fn score_value(app: &mut App) -> usize {
+    board_value(app, PLAYER_ONE).0
+}
+
+/// 名前を指定したスコアボードのスコアと表示テキストを返す関数
+fn board_value(app: &mut App, name: &str) -> (usize, String) {
+    let entity = board(app, name);
+    let score = **app.world().get::<Score>(entity).unwrap();
+    (score, app.world().get::<TextSpan>(entity).unwrap().0.clone())
+}
+
+/// 表示の数え上げが終わるのを待ってから、1Pのスコアとその表示テキストを返す関数
+fn score(app: &mut App) -> (usize, String) {
+    let entity = board(app, PLAYER_ONE);
+    for _ in 0..MAX_ROLL_TICKS {
+        let rolling = app.world().get::<RollingScore>(entity).unwrap();
+        if rolling.value() == **app.world().get::<Score>(entity).unwrap() {
+            break;
+        }
+        app.update();
+    }
+    board_value(app, PLAYER_ONE)
+}
+
+#[test]
+fn add_score_key_increments_score() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    assert_eq!(score(&mut app), (0, "0".to_string()));
+
+    common::press_key(&mut app, KEY_ADD_SCORE);
+    assert_eq!(score(&mut app), (1, "1".to_string()));
+
+    common::press_key(&mut app, KEY_ADD_SCORE);
+    common::press_key(&mut app, KEY_ADD_SCORE);
+    assert_eq!(score(&mut app), (3, "3".to_string()));
+}
+
+#[test]
+fn other_keys_do_not_change_score() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    common::press_key(&mut app, KeyCode::KeyZ);
+    common::advance(&mut app, 10);
+    assert_eq!(score(&mut app), (0, "0".to_string()));
+}
+
+#[test]
+fn new_record_asks_for_name_and_is_saved() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    let path = common::temp_path("scoreboard/new_record.ron");
+    let _ = fs::remove_file(&path);
+    app.insert_resource(HighScores::new(3, Some(path.clone())));
+
+    for _ in 0..3 {
+        common::press_key(&mut app, KEY_ADD_SCORE);
+    }
+    common::press_key(&mut app, KEY_FINISH_ROUND);
+    assert_eq!(score(&mut app), (0, "0".to_string()));
+    assert_eq!(app.world().resource::<NameEntry>().score, 3);
+
+    // 入力中はAキーもなまえの文字になる
+    common::type_text(&mut app, "BEVYA");
+    common::press_logical_key(&mut app, KeyCode::Backspace, Key::Backspace);
+    common::press_logical_key(&mut app, KEY_FINISH_ROUND, Key::Enter);
+    assert_eq!(score(&mut app), (0, "0".to_string()));
+    assert!(app.world().get_resource::<NameEntry>().is_none());
+
+    let entries = app.world().resource::<HighScores>().entries().to_vec();
+    assert_eq!(entries.len(), 1);
+    assert_eq!((entries[0].name.as_str(), entries[0].score), ("BEVY", 3));
+    assert_eq!(HighScores::load(&path, 3).unwrap().entries(), entries);
+
+    let mut query = app.world_mut().query_filtered::<&Text, With<HighScoreList>>();
+    let list = query.single(app.world()).unwrap();
+    assert!(list.contains("BEVY"), "{}", list.0);
+}
+
+#[test]
+fn high_scores_are_loaded_from_storage_root() {
+    let app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    let root = &app.world().resource::<StorageRoot>().0;
+    let high_scores = app.world().resource::<HighScores>();
+    assert_eq!(high_scores.path(), Some(root.join(DATA_DIR).join(HIGH_SCORES_FILE).as_path()));
+    assert!(high_scores.entries().is_empty());
+}
+
+#[test]
+fn zero_score_does_not_ask_for_name() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    app.insert_resource(HighScores::new(3, None));
+    common::press_key(&mut app, KEY_FINISH_ROUND);
+    assert!(app.world().get_resource::<NameEntry>().is_none());
+}
+
+/// 設定ファイルの読み込みを待ってから、得点のルールを差し替えたアプリを作成する関数
+fn app_with_rules(rules: ScoreRules) -> App {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    for _ in 0..100 {
+        if !app.world().resource::<Assets<ScoreRules>>().is_empty() {
+            break;
+        }
+        app.update();
+    }
+    app.update();
+    app.insert_resource(rules);
+    app
+}
+
+#[test]
+fn combo_raises_multiplier_and_decays() {
+    let mut app = app_with_rules(ScoreRules {
+        combo_window: 0.5,
+        combo_step: 2,
+        max_multiplier: 3,
+        combo_decay: 2,
+        bonuses: Vec::new(),
+    });
+
+    // 1, 1, 2, 2, 3, 3（上限）の順に倍率が上がる
+    for _ in 0..6 {
+        common::press_key(&mut app, KEY_ADD_SCORE);
+    }
+    assert_eq!(score_value(&mut app), 12);
+    assert_eq!(combo(&mut app, PLAYER_ONE), 6);
+    assert_eq!(combo_text(&mut app, PLAYER_ONE), "コンボ：6 ×3");
+
+    // 0.5秒ごとに2ずつ減る
+    common::advance(&mut app, 31);
+    assert_eq!(combo(&mut app, PLAYER_ONE), 4);
+    common::advance(&mut app, 60);
+    assert_eq!(combo(&mut app, PLAYER_ONE), 0);
+
+    common::press_key(&mut app, KEY_ADD_SCORE);
+    assert_eq!(score_value(&mut app), 13);
+    assert_eq!(score(&mut app), (13, "13".to_string()));
+}
+
+#[test]
+fn players_keep_separate_combos() {
+    let mut app = app_with_rules(ScoreRules {
+        combo_window: 10.0,
+        combo_step: 2,
+        max_multiplier: 4,
+        combo_decay: 1,
+        bonuses: Vec::new(),
+    });
+
+    // 交互に得点しても、それぞれのコンボは自分の得点だけで上がる
+    for _ in 0..3 {
+        common::press_key(&mut app, KEY_ADD_SCORE);
+        common::press_key(&mut app, KEY_ADD_SCORE_PLAYER_TWO);
+    }
+    assert_eq!(combo(&mut app, PLAYER_ONE), 3);
+    assert_eq!(combo(&mut app, PLAYER_TWO), 3);
+    // 倍率は1, 1, 2の順
+    assert_eq!(board_value(&mut app, PLAYER_ONE).0, 4);
+    assert_eq!(board_value(&mut app, PLAYER_TWO).0, 4);
+    assert_eq!(combo_text(&mut app, PLAYER_TWO), "コンボ：3 ×2");
+
+    // どのスコアボードも受け取らないイベントは、コンボにも数えず通知もしない
+    app.world_mut().resource_mut::<Events<ScoreAwarded>>().clear();
+    app.world_mut().send_event(ScoreEvent::new(5, ScoreSource::Pickup).to("3P"));
+    app.update();
+    assert!(app.world().resource::<Events<ScoreAwarded>>().is_empty());
+    assert_eq!(combo(&mut app, PLAYER_ONE), 3);
+    assert_eq!(combo(&mut app, PLAYER_TWO), 3);
+
+    // ラウンドを終えると、全てのコンボが戻る
+    common::press_key(&mut app, KEY_FINISH_ROUND);
+    assert_eq!(combo(&mut app, PLAYER_ONE), 0);
+    assert_eq!(combo(&mut app, PLAYER_TWO), 0);
+}
+
+#[test]
+fn score_events_apply_bonus_rules() {
+    let mut app = app_with_rules(ScoreRules {
+        bonuses: vec![
+            BonusRule { source: Some(ScoreSource::Pickup), every_combo: 2, points: 100 },
+            BonusRule { source: None, every_combo: 3, points: 1000 },
+        ],
+        ..Default::default()
+    });
+
+    for source in [ScoreSource::Collision, ScoreSource::Pickup, ScoreSource::Pickup] {
+        app.world_mut().send_event(ScoreEvent::new(5, source).to(PLAYER_ONE));
+        app.update();
+    }
+    let events = app.world().resource::<Events<ScoreAwarded>>();
+    let awarded: Vec<usize> = events.get_cursor().read(events).map(|event| event.points).collect();
+    assert_eq!(awarded.last(), Some(&1005));
+
+    assert_eq!(score(&mut app), (5 + 105 + 1005, "1115".to_string()));
+}
+
+#[test]
+fn huge_scores_saturate_instead_of_overflowing() {
+    let mut app = app_with_rules(ScoreRules {
+        combo_step: 1,
+        max_multiplier: MAX_MULTIPLIER_LIMIT,
+        ..Default::default()
+    });
+
+    for _ in 0..3 {
+        app.world_mut().send_event(ScoreEvent::new(usize::MAX / 2, ScoreSource::Pickup).to(PLAYER_ONE));
+        app.update();
+    }
+    let events = app.world().resource::<Events<ScoreAwarded>>();
+    let awarded: Vec<usize> = events.get_cursor().read(events).map(|event| event.points).collect();
+    assert_eq!(awarded.last(), Some(&usize::MAX));
+    assert_eq!(board_value(&mut app, PLAYER_ONE).0, usize::MAX);
+}
+
+#[test]
+fn displayed_score_rolls_toward_score() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    app.world_mut().send_event(ScoreEvent::new(100, ScoreSource::Pickup).to(PLAYER_ONE));
+    app.update();
+
+    // 表示は少しずつ数え上げられ、その間は文字が大きくなる
+    let entity = board(&mut app, PLAYER_ONE);
+    let mut query = app.world_mut().query::<(&Score, &RollingScore, &TextSpan, &TextFont)>();
+    let (current, rolling, span, font) = query.get(app.world(), entity).unwrap();
+    assert_eq!(**current, 100);
+    assert!(0 < rolling.value() && rolling.value() < 100, "{rolling:?}");
+    assert_eq!(span.0, rolling.value().to_string());
+    assert!(font.font_size > rolling.font_size, "{}", font.font_size);
+
+    assert_eq!(score(&mut app), (100, "100".to_string()));
+    common::advance(&mut app, 30);
+    let mut query = app.world_mut().query::<(&RollingScore, &TextFont)>();
+    let (rolling, font) = query.get(app.world(), entity).unwrap();
+    assert_eq!(font.font_size, rolling.font_size);
+}
+
+#[test]
+fn score_popups_float_up_and_disappear() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    // 1Pが受け取る、名前の無い入力の得点
+    app.world_mut().send_event(ScoreEvent::new(5, ScoreSource::Input).at(Vec2::new(10.0, 20.0)));
+    app.world_mut().send_event(ScoreEvent::new(5, ScoreSource::Input));
+    app.update();
+
+    // 位置のある得点だけポップアップが表示される
+    let mut query = app.world_mut().query_filtered::<(&Text2d, &Transform), With<ScorePopup>>();
+    let popups: Vec<(String, Vec2)> = query.iter(app.world())
+        .map(|(text, transform)| (text.0.clone(), transform.translation.truncate()))
+        .collect();
+    assert_eq!(popups.len(), 1);
+    assert_eq!(popups[0].0, "+5");
+    assert!(popups[0].1.abs_diff_eq(Vec2::new(10.0, 20.0), 1.0), "{popups:?}");
+
+    common::advance(&mut app, 30);
+    let mut query = app.world_mut().query_filtered::<(&Transform, &TextColor), With<ScorePopup>>();
+    let (transform, color) = query.single(app.world()).unwrap();
+    assert!(transform.translation.y > 20.0);
+    assert!(color.0.alpha() < 1.0);
+
+    common::advance(&mut app, 40);
+    assert_eq!(common::count::<With<ScorePopup>>(&mut app), 0);
+}
+
+#[test]
+fn players_score_on_their_own_boards() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    common::press_key(&mut app, KEY_ADD_SCORE);
+    common::press_key(&mut app, KEY_ADD_SCORE_PLAYER_TWO);
+    common::press_key(&mut app, KEY_ADD_SCORE_PLAYER_TWO);
+    assert_eq!(score_value(&mut app), 1);
+    assert_eq!(board_value(&mut app, PLAYER_TWO).0, 2);
+
+    // 名前の無いイベントは、発生源を受け付けるスコアボードだけに加算される
+    app.world_mut().send_event(ScoreEvent::new(1000, ScoreSource::Input));
+    app.world_mut().send_event(ScoreEvent::new(1000, ScoreSource::Collision));
+    common::advance(&mut app, 60);
+    assert_eq!(score(&mut app), (1001, "1001".to_string()));
+    // 2Pは全角の数字と区切り文字で表示される
+    assert_eq!(board_value(&mut app, PLAYER_TWO), (2, "２".to_string()));
+    assert_eq!(board_value(&mut app, LIVES), (MAX_LIVES, "０３".to_string()));
+}
+
+#[test]
+fn round_time_is_shown_as_minutes_and_seconds() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    assert_eq!(board_value(&mut app, TIME).1, "00:00");
+    common::advance(&mut app, 65);
+    assert_eq!(board_value(&mut app, TIME), (1, "00:01".to_string()));
+
+    common::press_key(&mut app, KEY_FINISH_ROUND);
+    assert_eq!(board_value(&mut app, TIME), (0, "00:00".to_string()));
+}
+
+#[test]
+fn losing_all_lives_finishes_round() {
+    let mut app = common::demo_app(Demo::Scoreboard, ScoreboardDemoPlugin);
+    app.insert_resource(HighScores::new(3, None));
+    common::press_key(&mut app, KEY_ADD_SCORE_PLAYER_TWO);
+    common::press_key(&mut app, KEY_ADD_SCORE_PLAYER_TWO);
+
+    for _ in 0..MAX_LIVES - 1 {
+        common::press_key(&mut app, KEY_LOSE_LIFE);
+    }
+    assert_eq!(board_value(&mut app, LIVES), (1, "０１".to_string()));
+    assert!(app.world().get_resource::<NameEntry>().is_none());
+
+    // 最後のライフで、最も高いプレイヤーのスコアのなまえの入力になる
+    common::press_key(&mut app, KEY_LOSE_LIFE);
+    assert_eq!(app.world().resource::<NameEntry>().score, 2);
+    assert_eq!(board_value(&mut app, LIVES).0, MAX_LIVES);
+    assert_eq!(board_value(&mut app, PLAYER_TWO).0, 0);
+}
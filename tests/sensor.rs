@@ -0,0 +1,82 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::physics::{
+    Collider,
+    CollidingEntities,
+    Sensor,
+    SensorEntered,
+    SensorExited,
+    SensorStayed,
+    Velocity,
+};
+
+const SPEED: f32 = 600.0;
+
+/// オブザーバーに通知されたイベントを記録するリソース
+#[derive(Resource, Default)]
+struct Received {
+    entered: Vec<SensorEntered>,
+    stayed: usize,
+    exited: Vec<SensorExited>,
+}
+
+/// センサーの中を通り抜けるボールを生成したアプリを作成する関数
+/// ボールは1ティックで10進み、センサーとは4、5ティック重なる
+fn ball_through_sensor() -> (App, Entity, Entity) {
+    let mut app = common::physics_app();
+    app.init_resource::<Received>();
+    let sensor = app.world_mut().spawn((
+        Transform::default(),
+        Collider::rectangle(40.0, 40.0),
+        Sensor,
+    ))
+    .observe(|trigger: Trigger<SensorEntered>, mut received: ResMut<Received>| {
+        received.entered.push(*trigger.event());
+    })
+    .observe(|_: Trigger<SensorStayed>, mut received: ResMut<Received>| {
+        received.stayed += 1;
+    })
+    .observe(|trigger: Trigger<SensorExited>, mut received: ResMut<Received>| {
+        received.exited.push(*trigger.event());
+    })
+    .id();
+    let ball = app.world_mut().spawn((
+        Transform::from_xyz(-50.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(SPEED, 0.0)),
+    )).id();
+    app.update();
+    (app, sensor, ball)
+}
+
+#[test]
+fn sensor_does_not_change_velocity() {
+    let (mut app, _, ball) = ball_through_sensor();
+
+    common::advance(&mut app, 10);
+    assert_eq!(app.world().get::<Velocity>(ball).unwrap().0, Vec2::new(SPEED, 0.0));
+}
+
+#[test]
+fn colliding_entities_lists_overlaps() {
+    let (mut app, sensor, ball) = ball_through_sensor();
+
+    common::advance(&mut app, 5);
+    assert!(app.world().get::<CollidingEntities>(sensor).unwrap().contains(&ball));
+
+    common::advance(&mut app, 10);
+    assert!(app.world().get::<CollidingEntities>(sensor).unwrap().is_empty());
+}
+
+#[test]
+fn sensor_sends_enter_stay_and_exit() {
+    let (mut app, sensor, ball) = ball_through_sensor();
+
+    common::advance(&mut app, 15);
+    let received = app.world().resource::<Received>();
+    assert_eq!(received.entered, [SensorEntered { sensor, entity: ball }]);
+    assert_eq!(received.exited, [SensorExited { sensor, entity: ball }]);
+    assert!((3..=4).contains(&received.stayed), "{}", received.stayed);
+}
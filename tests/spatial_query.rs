@@ -0,0 +1,134 @@
+mod common;
+
+use bevy::{
+    prelude::*,
+    ecs::system::RunSystemOnce,
+    math::bounding::Aabb2d,
+};
+
+use example_bevy::physics::{Collider, CollisionLayers, SpatialQuery, SpatialQueryFilter};
+
+/// 形状を並べ、広域判定を更新したアプリを作成する関数
+/// x軸上の左から、グループ1に属する円と矩形、グループ2に属する円を並べる
+fn shapes_on_x_axis() -> (App, [Entity; 3]) {
+    let mut app = common::physics_app();
+    let circle = app.world_mut().spawn((
+        Transform::from_xyz(50.0, 0.0, 0.0),
+        Collider::circle(10.0),
+        CollisionLayers::new(0b01, CollisionLayers::ALL),
+    )).id();
+    let square = app.world_mut().spawn((
+        Transform::from_xyz(100.0, 0.0, 0.0),
+        Collider::rectangle(20.0, 20.0),
+        CollisionLayers::new(0b01, CollisionLayers::ALL),
+    )).id();
+    let layered = app.world_mut().spawn((
+        Transform::from_xyz(150.0, 0.0, 0.0),
+        Collider::circle(10.0),
+        CollisionLayers::new(0b10, CollisionLayers::ALL),
+    )).id();
+    common::advance(&mut app, 2);
+    (app, [circle, square, layered])
+}
+
+#[test]
+fn raycast_returns_nearest_hit() {
+    let (mut app, [circle, square, _]) = shapes_on_x_axis();
+
+    let hit = app.world_mut().run_system_once(|query: SpatialQuery| {
+        query.raycast(Vec2::ZERO, Dir2::X, 500.0, &SpatialQueryFilter::default())
+    }).unwrap().unwrap();
+    assert_eq!(hit.entity, circle);
+    assert!((hit.distance - 40.0).abs() < 1e-4, "{hit:?}");
+    assert!(hit.normal.abs_diff_eq(Vec2::NEG_X, 1e-4), "{hit:?}");
+
+    // 最初に当たる形状を除くと、次の形状に当たる
+    let hit = app.world_mut().run_system_once(move |query: SpatialQuery| {
+        let filter = SpatialQueryFilter::default().with_excluded([circle]);
+        query.raycast(Vec2::ZERO, Dir2::X, 500.0, &filter)
+    }).unwrap().unwrap();
+    assert_eq!(hit.entity, square);
+    assert!((hit.distance - 90.0).abs() < 1e-4, "{hit:?}");
+
+    // 届かない場合と、外れる場合
+    let miss = app.world_mut().run_system_once(|query: SpatialQuery| {
+        let filter = SpatialQueryFilter::default();
+        (query.raycast(Vec2::ZERO, Dir2::X, 30.0, &filter), query.raycast(Vec2::ZERO, Dir2::Y, 500.0, &filter))
+    }).unwrap();
+    assert_eq!(miss, (None, None));
+}
+
+#[test]
+fn raycast_filters_by_layers() {
+    let (mut app, [.., layered]) = shapes_on_x_axis();
+
+    let hit = app.world_mut().run_system_once(|query: SpatialQuery| {
+        query.raycast(Vec2::ZERO, Dir2::X, 500.0, &SpatialQueryFilter::from_mask(0b10))
+    }).unwrap().unwrap();
+    assert_eq!(hit.entity, layered);
+    assert!((hit.distance - 140.0).abs() < 1e-4, "{hit:?}");
+}
+
+#[test]
+fn shapecast_stops_at_first_contact() {
+    let (mut app, [circle, ..]) = shapes_on_x_axis();
+
+    let hit = app.world_mut().run_system_once(|query: SpatialQuery| {
+        let shape = Collider::circle(5.0);
+        query.shapecast(&shape, Isometry2d::IDENTITY, Dir2::X, 500.0, &SpatialQueryFilter::default())
+    }).unwrap().unwrap();
+    assert_eq!(hit.entity, circle);
+    assert!((hit.distance - 35.0).abs() < 1e-3, "{hit:?}");
+    assert!(hit.normal.abs_diff_eq(Vec2::NEG_X, 1e-3), "{hit:?}");
+}
+
+#[test]
+fn point_and_aabb_queries() {
+    let (mut app, [circle, square, layered]) = shapes_on_x_axis();
+
+    let (inside, outside, overlapping) = app.world_mut().run_system_once(|query: SpatialQuery| {
+        let filter = SpatialQueryFilter::default();
+        (
+            query.point_query(Vec2::new(105.0, 5.0), &filter),
+            // 円の境界ボックスの角には含まれるが、円には含まれない点
+            query.point_query(Vec2::new(59.0, 9.0), &filter),
+            query.aabb_query(Aabb2d::new(Vec2::new(75.0, 0.0), Vec2::new(20.0, 5.0)), &filter),
+        )
+    }).unwrap();
+    assert_eq!(inside, [square]);
+    assert!(outside.is_empty());
+    assert_eq!(overlapping, [circle, square]);
+
+    let all = app.world_mut().run_system_once(|query: SpatialQuery| {
+        query.aabb_query(Aabb2d::new(Vec2::new(100.0, 0.0), Vec2::new(100.0, 100.0)), &SpatialQueryFilter::default())
+    }).unwrap();
+    assert_eq!(all, [circle, square, layered]);
+}
+
+#[test]
+fn raycast_hits_segments_and_polygons() {
+    let mut app = common::physics_app();
+    let segment = app.world_mut().spawn((
+        Transform::from_xyz(50.0, 0.0, 0.0),
+        Collider::segment(Vec2::new(0.0, -20.0), Vec2::new(0.0, 20.0)),
+    )).id();
+    let polygon = app.world_mut().spawn((
+        Transform::from_xyz(0.0, 50.0, 0.0),
+        Collider::polygon(&[Vec2::new(-20.0, 0.0), Vec2::new(20.0, 0.0), Vec2::new(0.0, 20.0)]).unwrap(),
+    )).id();
+    common::advance(&mut app, 2);
+
+    let hits = app.world_mut().run_system_once(|query: SpatialQuery| {
+        let filter = SpatialQueryFilter::default();
+        (query.raycast(Vec2::ZERO, Dir2::X, 500.0, &filter), query.raycast(Vec2::ZERO, Dir2::Y, 500.0, &filter))
+    }).unwrap();
+    let (Some(to_segment), Some(to_polygon)) = hits else {
+        panic!("{hits:?}");
+    };
+    assert_eq!(to_segment.entity, segment);
+    assert!((to_segment.distance - 50.0).abs() < 1e-4, "{to_segment:?}");
+    assert_eq!(to_segment.normal, Vec2::NEG_X);
+    assert_eq!(to_polygon.entity, polygon);
+    assert!((to_polygon.distance - 50.0).abs() < 1e-4, "{to_polygon:?}");
+    assert_eq!(to_polygon.normal, Vec2::NEG_Y);
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use bevy::prelude::*;
+
+use example_bevy::{
+    WINDOW_SIZE,
+    demos::{Demo, velocity::VelocityDemoPlugin},
+    physics::{Position, Velocity},
+};
+
+/// 四角形の位置と速度を返す関数
+fn square(app: &mut App) -> (Vec2, Vec2) {
+    let mut query = app.world_mut().query::<(&Position, &Velocity)>();
+    let (position, velocity) = query.single(app.world()).unwrap();
+    (**position, **velocity)
+}
+
+#[test]
+fn square_moves_by_fixed_timestep() {
+    let mut app = common::demo_app(Demo::Velocity, VelocityDemoPlugin);
+    common::advance(&mut app, 60);
+    let (position, velocity) = square(&mut app);
+    // セットアップ後、60ティック（1秒）で速度の分だけ進む
+    assert!((position - velocity).length() < 1e-3, "{position}");
+}
+
+#[test]
+fn wall_collision_flips_velocity() {
+    let mut app = common::demo_app(Demo::Velocity, VelocityDemoPlugin);
+    let (_, velocity) = square(&mut app);
+
+    let mut query = app.world_mut().query_filtered::<&mut Position, With<Velocity>>();
+    query.single_mut(app.world_mut()).unwrap().x = WINDOW_SIZE.x / 2.0;
+    app.update();
+
+    let (_, flipped) = square(&mut app);
+    assert_eq!(flipped, Vec2::new(-velocity.x, velocity.y));
+}
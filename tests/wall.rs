@@ -0,0 +1,119 @@
+mod common;
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
+
+use example_bevy::{
+    WINDOW_SIZE,
+    physics::{Collider, Contacts, PlayArea, Position, Velocity, WallHit, WallSide, WindowBounds},
+};
+
+/// 受け取った壁のイベントを記録するリソース
+#[derive(Resource, Default)]
+struct Received(Vec<WallHit>);
+
+#[test]
+fn window_bounds_spawns_walls_as_children() {
+    let mut app = common::physics_app();
+    let bounds = app.world_mut().spawn(WindowBounds).id();
+    app.update();
+
+    let mut query = app.world_mut().query::<(&WallSide, &ChildOf)>();
+    let mut sides: Vec<WallSide> = query.iter(app.world())
+        .inspect(|(_, child_of)| assert_eq!(child_of.parent(), bounds))
+        .map(|(side, _)| *side)
+        .collect();
+    sides.sort_by_key(|side| WallSide::ALL.iter().position(|s| s == side));
+    assert_eq!(sides, WallSide::ALL);
+
+    // 壁同士は重なっていても接触しない
+    common::advance(&mut app, 2);
+    assert!(app.world().resource::<Contacts>().is_empty());
+
+    // 親を消すと壁も消える
+    app.world_mut().despawn(bounds);
+    assert_eq!(common::count::<With<WallSide>>(&mut app), 0);
+}
+
+#[test]
+fn ball_hits_window_bounds() {
+    let mut app = common::physics_app();
+    app
+        .init_resource::<Received>()
+        .add_observer(|trigger: Trigger<WallHit>, mut received: ResMut<Received>| {
+            received.0.push(*trigger.event());
+        });
+    app.world_mut().spawn(WindowBounds);
+    let ball = app.world_mut().spawn((
+        Transform::from_xyz(0.0, WINDOW_SIZE.y / 2.0 - 12.0, 0.0),
+        Collider::circle(10.0),
+        Velocity(Vec2::new(0.0, 300.0)),
+    )).id();
+    app.update();
+
+    common::advance(&mut app, 3);
+    assert_eq!(app.world().get::<Velocity>(ball).unwrap().0, Vec2::new(0.0, -300.0));
+    let received = &app.world().resource::<Received>().0;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].entity, ball);
+    assert_eq!(received[0].side, WallSide::Top);
+    assert_eq!(received[0].normal, Vec2::NEG_Y);
+    assert_eq!(received[0].point.y, WINDOW_SIZE.y / 2.0);
+}
+
+#[test]
+fn ball_bounces_off_static_obstacles() {
+    let mut app = common::physics_app();
+    // 斜めの線分と、三角形の障害物
+    app.world_mut().spawn((
+        Transform::from_xyz(100.0, 0.0, 0.0),
+        Collider::segment(Vec2::new(-50.0, -50.0), Vec2::new(50.0, 50.0)),
+    ));
+    app.world_mut().spawn((
+        Transform::from_xyz(-100.0, 0.0, 0.0),
+        Collider::polygon(&[Vec2::new(0.0, -30.0), Vec2::new(0.0, 30.0), Vec2::new(-30.0, 0.0)]).unwrap(),
+    ));
+    let right = app.world_mut().spawn((
+        Transform::from_xyz(60.0, 20.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(300.0, 0.0)),
+    )).id();
+    let left = app.world_mut().spawn((
+        Transform::from_xyz(-70.0, 0.0, 0.0),
+        Collider::circle(5.0),
+        Velocity(Vec2::new(-300.0, 0.0)),
+    )).id();
+    app.update();
+
+    // 45度の線分で向きが直角に変わり、三角形の辺では跳ね返る
+    common::advance(&mut app, 20);
+    let velocity = app.world().get::<Velocity>(right).unwrap().0;
+    assert!(velocity.distance(Vec2::new(0.0, 300.0)) < 1e-2, "{velocity}");
+    let velocity = app.world().get::<Velocity>(left).unwrap().0;
+    assert_eq!(velocity, Vec2::new(300.0, 0.0));
+}
+
+#[test]
+fn walls_follow_resized_window() {
+    let mut app = common::physics_app();
+    let window = app.world_mut().spawn((Window::default(), PrimaryWindow)).id();
+    app.world_mut().spawn(WindowBounds);
+    app.update();
+    let size = app.world().get::<Window>(window).unwrap().size();
+    assert_eq!(app.world().resource::<PlayArea>().size, size);
+
+    app.world_mut().send_event(WindowResized { window, width: 800.0, height: 600.0 });
+    common::advance(&mut app, 2);
+    assert_eq!(app.world().resource::<PlayArea>().size, Vec2::new(800.0, 600.0));
+
+    // 右の壁の内側の面が、新しい画面の右端に来る
+    let mut query = app.world_mut().query::<(&WallSide, &Position, &Collider)>();
+    let (_, position, collider) = query.iter(app.world())
+        .find(|(side, ..)| **side == WallSide::Right)
+        .unwrap();
+    let aabb = collider.aabb(Isometry2d::from_translation(**position));
+    assert_eq!(aabb.min.x, 400.0);
+    assert_eq!(aabb.max.y - aabb.min.y, 600.0 + 200.0);
+}